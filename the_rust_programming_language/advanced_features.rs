@@ -318,6 +318,46 @@ impl Add<Meters> for Millimeters {
 // To add Millimeters and Meters, we specify impl Add<Meters> to set the value of the RHS type parameter instead
 // of using the default of Self.
 
+// PartialEq and PartialOrd take an Rhs type parameter the same way, so we can compare the two units directly and
+// have Millimeters(1000) == Meters(1) be true. Both sides are converted to millimeters, the smaller unit, so no
+// rounding ever happens: going the other way would truncate, making Millimeters(1500) look equal to Meters(1).
+// The conversion is done in u64 so a large Meters value can't overflow the multiplication.
+use std::cmp::Ordering;
+
+impl Meters {
+    fn as_millimeters(&self) -> u64 {
+        u64::from(self.0) * 1000
+    }
+}
+
+impl PartialEq<Meters> for Millimeters {
+    fn eq(&self, other: &Meters) -> bool {
+        u64::from(self.0) == other.as_millimeters()
+    }
+}
+
+impl PartialOrd<Meters> for Millimeters {
+    fn partial_cmp(&self, other: &Meters) -> Option<Ordering> {
+        u64::from(self.0).partial_cmp(&other.as_millimeters())
+    }
+}
+
+#[test]
+fn millimeters_equal_to_meters() {
+    assert!(Millimeters(1000) == Meters(1));
+    assert!(Millimeters(1500) != Meters(1));
+}
+
+#[test]
+fn millimeters_less_than_meters() {
+    assert!(Millimeters(999) < Meters(1));
+}
+
+#[test]
+fn millimeters_greater_than_meters() {
+    assert!(Millimeters(2001) > Meters(2));
+}
+
 // You’ll use default type parameters in two main ways:
 
 // - To extend a type without breaking existing code
@@ -379,24 +419,190 @@ fn main() {
     person.fly(); // or Human::fly(&person)
 }
 
+// A raw u8 will happily hold an age of 255. Wrapping it in a HumanAge newtype lets us reject implausible values
+// once, in new, so every HumanAge we come across afterwards is known to be valid. Implementing Deref to u8 means
+// it can still be read like the number it wraps, and Display lets us print it directly.
+use std::fmt;
+use std::ops::Deref;
+
+const MAX_HUMAN_AGE: u8 = 150;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HumanAge(u8);
+
+impl HumanAge {
+    fn new(age: u8) -> Result<HumanAge, String> {
+        if age > MAX_HUMAN_AGE {
+            return Err(format!("{} is not a plausible age, the maximum is {}", age, MAX_HUMAN_AGE));
+        }
+
+        Ok(HumanAge(age))
+    }
+}
+
+impl Deref for HumanAge {
+    type Target = u8;
+
+    fn deref(&self) -> &u8 {
+        &self.0
+    }
+}
+
+impl fmt::Display for HumanAge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 struct Person {
     name: String,
-    age: u8,
+    age: HumanAge,
 }
 
 impl Person {
     fn new() -> Person {
         Person {
             name: String::from("josce"),
-            age: 24,
+            age: HumanAge(24),
         }
     }
 
     fn get_age(&self) -> u8 {
-        self.age
+        *self.age
+    }
+}
+
+#[test]
+fn valid_human_age() {
+    let age = HumanAge::new(42).unwrap();
+
+    assert_eq!(*age, 42);
+    assert_eq!(age.to_string(), "42");
+    assert_eq!(Person::new().get_age(), 24);
+}
+
+#[test]
+fn out_of_range_human_age() {
+    assert_eq!(HumanAge::new(151), Err(String::from("151 is not a plausible age, the maximum is 150")));
+}
+
+// To sort people, Person needs a total ordering. We compare ages first and fall back to names for people who are the same
+// age, which keeps Eq consistent with Ord: two people are only equal when both fields match. oldest then reuses that
+// ordering through Iterator::max instead of comparing ages by hand:
+use std::cmp::Ordering;
+
+impl PartialEq for Person {
+    fn eq(&self, other: &Person) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Person {}
+
+impl PartialOrd for Person {
+    fn partial_cmp(&self, other: &Person) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Person {
+    fn cmp(&self, other: &Person) -> Ordering {
+        (*self.age, &self.name).cmp(&(*other.age, &other.name))
+    }
+}
+
+fn oldest(people: &[Person]) -> Option<&Person> {
+    people.iter().max()
+}
+
+#[cfg(test)]
+fn person(name: &str, age: u8) -> Person {
+    Person {
+        name: String::from(name),
+        age: HumanAge::new(age).unwrap(),
     }
 }
 
+#[test]
+fn sort_people_by_age_then_name() {
+    let mut people = vec![person("carol", 41), person("bob", 24)];
+    people.push(person("alice", 24));
+
+    people.sort();
+
+    let names: Vec<&str> = people.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["alice", "bob", "carol"]);
+}
+
+#[test]
+fn find_the_oldest_person() {
+    let people = vec![person("bob", 24), person("carol", 41), person("alice", 24)];
+
+    assert_eq!(oldest(&people).map(|p| p.name.as_str()), Some("carol"));
+    assert!(oldest(&[]).is_none());
+}
+
+// A person can also be saved as a single line of key=value fields separated by semicolons, like name=alice;age=24.
+// from_line reads the fields back in any order and builds the age through HumanAge::new, so a line can't sneak in an
+// age that new would have rejected. It reports a field that's missing, one it doesn't know, one that appears twice, or a
+// piece of the line that isn't key=value at all. Nothing is escaped, so a name containing ; or = won't read back:
+impl Person {
+    fn to_line(&self) -> String {
+        format!("name={};age={}", self.name, self.age)
+    }
+
+    fn from_line(s: &str) -> Result<Person, String> {
+        let mut name = None;
+        let mut age = None;
+
+        for field in s.trim().split(';') {
+            let (key, value) = field
+                .split_once('=')
+                .ok_or_else(|| format!("field {:?} is not key=value", field))?;
+            let slot = match key {
+                "name" => &mut name,
+                "age" => &mut age,
+                _ => return Err(format!("unexpected field {:?}", key)),
+            };
+            if slot.replace(value).is_some() {
+                return Err(format!("field {:?} appears more than once", key));
+            }
+        }
+
+        let name = name.ok_or("missing field \"name\"")?;
+        let age = age.ok_or("missing field \"age\"")?;
+        let age: u8 = age.parse().map_err(|_| format!("age {:?} is not a number", age))?;
+
+        Ok(Person {
+            name: String::from(name),
+            age: HumanAge::new(age)?,
+        })
+    }
+}
+
+#[test]
+fn person_line_round_trip() {
+    let line = person("alice", 24).to_line();
+    assert_eq!(line, "name=alice;age=24");
+
+    let alice = Person::from_line(&line).unwrap();
+    assert!(alice == person("alice", 24));
+    assert!(Person::from_line("age=41;name=carol").unwrap() == person("carol", 41));
+}
+
+#[test]
+fn person_line_that_is_malformed() {
+    let err = |line: &str| Person::from_line(line).err().unwrap();
+
+    assert_eq!(err("name=alice"), "missing field \"age\"");
+    assert_eq!(err("age=24"), "missing field \"name\"");
+    assert_eq!(err("name=alice;age=24;email=a@example.com"), "unexpected field \"email\"");
+    assert_eq!(err("name=alice;age=24;age=25"), "field \"age\" appears more than once");
+    assert_eq!(err("name=alice;24"), "field \"24\" is not key=value");
+    assert_eq!(err("name=alice;age=old"), "age \"old\" is not a number");
+    assert_eq!(err("name=alice;age=200"), "200 is not a plausible age, the maximum is 150");
+}
+
 // This code is for an animal shelter that wants to name all puppies Spot, which is implemented
 // in the baby_name associated function that is defined on Dog. The Dog type also implements
 // the trait Animal, which describes characteristics that all animals have. Baby dogs are called
@@ -519,6 +725,36 @@ fn main() {
 // would be a solution. If we don’t want the Wrapper type to have all the methods of the inner type—for example,
 // to restrict the Wrapper type’s behavior—we would have to implement just the methods we do want manually.
 
+// Here we do want Wrapper to act like its Vec<String>, so it implements Deref with Target = Vec<String> for the read-only
+// methods like len and iter, and DerefMut so callers can push onto it directly. Any change made through DerefMut shows up
+// the next time the Wrapper is displayed. Keep in mind that DerefMut hands out the inner Vec<String> with nothing in between,
+// so a wrapper that had rules about its contents couldn't enforce them anymore; Wrapper has no such rules, so that's fine:
+use std::ops::{Deref, DerefMut};
+
+impl Deref for Wrapper {
+    type Target = Vec<String>;
+
+    fn deref(&self) -> &Vec<String> {
+        &self.0
+    }
+}
+
+impl DerefMut for Wrapper {
+    fn deref_mut(&mut self) -> &mut Vec<String> {
+        &mut self.0
+    }
+}
+
+#[test]
+fn push_onto_a_wrapper_through_deref_mut() {
+    let mut w = Wrapper(vec![String::from("hello")]);
+
+    w.push(String::from("world"));
+
+    assert_eq!(w.len(), 2);
+    assert_eq!(w.to_string(), "[hello, world]");
+}
+
 // Creating Type Synonyms with Type Aliases
 // Along with the newtype pattern, Rust provides the ability to declare a type alias to give an existing type
 // another name. For this we use the type keyword. For example, we can create the alias Kilometers to i32 like so:
@@ -715,6 +951,7 @@ let list_of_strings: Vec<String> = list_of_numbers
 // actually implemented as functions returning an instance that’s constructed from their arguments. We can use
 // these initializer functions as function pointers that implement the closure traits, which means we can specify
 // the initializer functions as arguments for methods that take closures, like so:
+#[derive(Debug, PartialEq)]
 enum Status {
     Value(u32),
     Stop,
@@ -724,6 +961,36 @@ let list_of_statuses: Vec<Status> = (0u32..20)
     .map(Status::Value)
     .collect();
 
+// Status::Value only works as a function pointer because every u32 makes a valid Status. Going the other way from an
+// i32 can fail, so instead of From we implement TryFrom, whose try_from returns a Result. Zero and up become Value,
+// -1 is used to mean Stop, and any other negative number is an error:
+use std::convert::TryFrom;
+
+impl TryFrom<i32> for Status {
+    type Error = String;
+
+    fn try_from(n: i32) -> Result<Self, Self::Error> {
+        match n {
+            n if n >= 0 => Ok(Status::Value(n as u32)),
+            -1 => Ok(Status::Stop),
+            n => Err(format!("{} is not a valid status", n)),
+        }
+    }
+}
+
+#[test]
+fn status_from_non_negative_and_minus_one() {
+    assert_eq!(Status::try_from(0), Ok(Status::Value(0)));
+    assert_eq!(Status::try_from(42), Ok(Status::Value(42)));
+    assert_eq!(Status::try_from(-1), Ok(Status::Stop));
+}
+
+#[test]
+fn status_from_other_negatives_is_an_error() {
+    assert_eq!(Status::try_from(-2), Err(String::from("-2 is not a valid status")));
+    assert!(Status::try_from(i32::MIN).is_err());
+}
+
 // And returning a closure:
 fn returns_closure() -> Box<dyn Fn(32) -> i32> {
     Box::new(|x| x + 1)
@@ -21,6 +21,45 @@ let x = x * 2;
 // (--release flag) rust performs two’s complement wrapping. Ex: when you have a
 // u8 you will go to 0 after 255 -> wraps back around.
 
+// To pick the behavior explicitly instead of depending on the build mode, use the
+// checked_, wrapping_, and saturating_ methods.
+mod overflow {
+    // None when the sum goes past 255, ex: 255 + 1 -> None
+    pub fn add_u8(a: u8, b: u8) -> Option<u8> {
+        a.checked_add(b)
+    }
+
+    // Wraps around past 255 in every build mode, ex: 255 + 1 -> 0
+    pub fn wrapping_add_u8(a: u8, b: u8) -> u8 {
+        a.wrapping_add(b)
+    }
+
+    // Stops at 255 instead of going past it, ex: 255 + 1 -> 255
+    pub fn saturating_add_u8(a: u8, b: u8) -> u8 {
+        a.saturating_add(b)
+    }
+
+    #[test]
+    fn checked_add_at_255() {
+        assert_eq!(add_u8(254, 1), Some(255));
+        assert_eq!(add_u8(255, 1), None);
+    }
+
+    #[test]
+    fn wrapping_add_at_255() {
+        assert_eq!(wrapping_add_u8(254, 1), 255);
+        assert_eq!(wrapping_add_u8(255, 1), 0);
+        assert_eq!(wrapping_add_u8(255, 10), 9);
+    }
+
+    #[test]
+    fn saturating_add_at_255() {
+        assert_eq!(saturating_add_u8(254, 1), 255);
+        assert_eq!(saturating_add_u8(255, 1), 255);
+        assert_eq!(saturating_add_u8(200, 100), 255);
+    }
+}
+
 // Number literals    Example
 // Decimal            98_222
 // Hex                0xff
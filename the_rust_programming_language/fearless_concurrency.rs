@@ -143,6 +143,40 @@ fn main() {
     handle.join().unwrap();
 }
 
+// join also hands back whatever the thread's closure returned, so the same pattern can spread work out and gather the
+// results back in. spawn_and_collect moves each job into its own thread and keeps the JoinHandles in the order the jobs
+// were given. It only starts joining once every thread is running, so the jobs run at the same time, and joining in
+// order means the results come back in the order the jobs were submitted no matter which thread finishes first. An
+// empty list of jobs spawns nothing and gives back an empty Vec:
+fn spawn_and_collect<T: Send + 'static>(jobs: Vec<Box<dyn FnOnce() -> T + Send>>) -> Vec<T> {
+    let handles: Vec<thread::JoinHandle<T>> = jobs.into_iter().map(thread::spawn).collect();
+
+    handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+}
+
+#[test]
+fn spawn_and_collect_keeps_submission_order() {
+    let jobs: Vec<Box<dyn FnOnce() -> u64 + Send>> = vec![
+        Box::new(|| (1..=20).product()),
+        Box::new(|| (1..=100).sum()),
+        Box::new(|| {
+            thread::sleep(std::time::Duration::from_millis(10));
+            (1..=10).map(|n| n * n).sum()
+        }),
+        Box::new(|| 2u64.pow(40)),
+    ];
+
+    assert_eq!(
+        spawn_and_collect(jobs),
+        vec![2_432_902_008_176_640_000, 5050, 385, 1_099_511_627_776]
+    );
+}
+
+#[test]
+fn spawn_and_collect_with_no_jobs() {
+    assert_eq!(spawn_and_collect::<i32>(Vec::new()), Vec::<i32>::new());
+}
+
 // Using Message Passing to Transfer Data Between Threads
 // One increasingly popular approach to ensuring safe concurrency is message passing, where threads or actors
 // communicate by sending each other messages containing data. Here’s the idea in a slogan from the Go language
@@ -434,6 +468,220 @@ fn main() {
 }
 // This will print "Result: 10"
 
+// Arc<Mutex<T>> works for any T that is Send, not just integers. Recall the generic cons list from Chapter 15:
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[derive(Debug, PartialEq)]
+enum List<T> {
+    Cons(T, Box<List<T>>),
+    Nil,
+}
+
+use crate::List::{Cons, Nil};
+
+// A SharedList wraps one in Arc<Mutex<List<T>>> and does its locking internally, so callers on any thread can clone the
+// handle and prepend to the same list without touching the Mutex themselves. push_front swaps the current list out for Nil
+// while it holds the lock, then puts it back as the tail of the new Cons:
+#[derive(Clone)]
+struct SharedList<T> {
+    list: Arc<Mutex<List<T>>>,
+}
+
+impl<T> SharedList<T> {
+    fn new() -> SharedList<T> {
+        SharedList {
+            list: Arc::new(Mutex::new(Nil)),
+        }
+    }
+
+    fn push_front(&self, value: T) {
+        let mut list = self.list.lock().unwrap();
+        let tail = std::mem::replace(&mut *list, Nil);
+
+        *list = Cons(value, Box::new(tail));
+    }
+
+    fn len(&self) -> usize {
+        let list = self.list.lock().unwrap();
+        let mut current = &*list;
+        let mut len = 0;
+
+        while let Cons(_, next) = current {
+            len += 1;
+            current = next;
+        }
+
+        len
+    }
+}
+
+#[test]
+fn push_onto_a_shared_list_from_many_threads() {
+    let shared = SharedList::new();
+    let mut handles = vec![];
+
+    for i in 0..8 {
+        let shared = shared.clone();
+        let handle = thread::spawn(move || {
+            for j in 0..10 {
+                shared.push_front(i * 10 + j);
+            }
+        });
+        handles.push(handle);
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(shared.len(), 80);
+}
+
+// The counter from Listing 16-15 can be wrapped up the same way. Counter keeps its Arc<Mutex<u64>> private, and increment
+// hands back the new count it saw while holding the lock, so two threads can never both see the same value:
+#[derive(Clone)]
+struct Counter {
+    count: Arc<Mutex<u64>>,
+}
+
+impl Counter {
+    fn new() -> Counter {
+        Counter {
+            count: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    fn increment(&self) -> u64 {
+        let mut count = self.count.lock().unwrap();
+        *count += 1;
+        *count
+    }
+
+    fn get(&self) -> u64 {
+        *self.count.lock().unwrap()
+    }
+
+    fn reset(&self) {
+        *self.count.lock().unwrap() = 0;
+    }
+}
+
+// Because increment returns the count it produced, a RateLimiter built on a Counter can hand out at most max permits until
+// it is reset. try_acquire takes the next count and succeeds only if that count is still within max; every later attempt
+// keeps counting and fails. No two threads can get the same count, so exactly max of them succeed:
+struct RateLimiter {
+    max: u64,
+    counter: Counter,
+}
+
+impl RateLimiter {
+    fn new(max: u64) -> RateLimiter {
+        RateLimiter {
+            max,
+            counter: Counter::new(),
+        }
+    }
+
+    fn try_acquire(&self) -> bool {
+        self.counter.increment() <= self.max
+    }
+
+    fn reset(&self) {
+        self.counter.reset();
+    }
+}
+
+#[test]
+fn rate_limiter_allows_exactly_max_acquisitions_across_threads() {
+    let limiter = Arc::new(RateLimiter::new(25));
+    let acquired = Counter::new();
+    let mut handles = vec![];
+
+    for _ in 0..10 {
+        let limiter = Arc::clone(&limiter);
+        let acquired = acquired.clone();
+        let handle = thread::spawn(move || {
+            for _ in 0..10 {
+                if limiter.try_acquire() {
+                    acquired.increment();
+                }
+            }
+        });
+        handles.push(handle);
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(acquired.get(), 25);
+    assert!(!limiter.try_acquire());
+}
+
+#[test]
+fn reset_rate_limiter_allows_acquiring_again() {
+    let limiter = RateLimiter::new(1);
+
+    assert!(limiter.try_acquire());
+    assert!(!limiter.try_acquire());
+
+    limiter.reset();
+
+    assert!(limiter.try_acquire());
+}
+
+// Drop works across threads too. Each Pooled value adds one to a live count when it's created and takes one away in drop,
+// so live_count says how many exist right now, whichever threads made or dropped them. The count is a static rather than
+// an Arc<Mutex<usize>> handed to every value: a static lives for the whole program, so every thread can already share a
+// reference to it and there's nothing for an Arc to keep alive. The Mutex is still what makes the updates safe:
+static LIVE_POOLED: Mutex<usize> = Mutex::new(0);
+
+struct Pooled {
+    id: usize,
+}
+
+impl Pooled {
+    fn new(id: usize) -> Pooled {
+        *LIVE_POOLED.lock().unwrap() += 1;
+        Pooled { id }
+    }
+}
+
+impl Drop for Pooled {
+    fn drop(&mut self) {
+        *LIVE_POOLED.lock().unwrap() -= 1;
+    }
+}
+
+fn live_count() -> usize {
+    *LIVE_POOLED.lock().unwrap()
+}
+
+#[test]
+fn live_count_returns_to_zero_across_threads() {
+    let kept: Vec<Pooled> = (0..3).map(Pooled::new).collect();
+    assert_eq!(live_count(), 3);
+
+    let handles: Vec<_> = (0..4)
+        .map(|thread_id| {
+            thread::spawn(move || {
+                let temporary: Vec<Pooled> = (0..10).map(|i| Pooled::new(thread_id * 100 + i)).collect();
+                drop(temporary);
+                Pooled::new(thread_id * 100)
+            })
+        })
+        .collect();
+
+    let returned: Vec<Pooled> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+    assert_eq!(live_count(), 7);
+    assert_eq!(returned.iter().map(|pooled| pooled.id).collect::<Vec<usize>>(), vec![0, 100, 200, 300]);
+
+    drop(returned);
+    drop(kept);
+    assert_eq!(live_count(), 0);
+}
+
 // Similarities Between RefCell<T>/Rc<T> and Mutex<T>/Arc<T>
 
 // You might have noticed that counter is immutable but we could get a mutable reference to the value inside it; this means
@@ -203,6 +203,65 @@ fn call_with_different_values() {
 // return a u32. We might want to cache the results of closures that take a string slice and return usize values, for example. To fix
 // this issue, try introducing more generic parameters to increase the flexibility of the Cacher functionality.
 
+// Memo does both. It's generic over the argument type A, the result type B, and the closure F, and it keeps one result per
+// distinct argument in a HashMap, so A has to be Eq + Hash to be a key. Unlike Cacher::value, call takes &self, so the map
+// sits in a RefCell. call only borrows the map for the lookup and the insert, never while f is running. It clones the
+// argument into the map and clones the cached result back out, so A and B also need to be Clone:
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+struct Memo<A, B, F>
+where
+    A: Eq + Hash + Clone,
+    B: Clone,
+    F: Fn(&A) -> B,
+{
+    f: F,
+    cache: RefCell<HashMap<A, B>>,
+}
+
+impl<A, B, F> Memo<A, B, F>
+where
+    A: Eq + Hash + Clone,
+    B: Clone,
+    F: Fn(&A) -> B,
+{
+    fn new(f: F) -> Memo<A, B, F> {
+        Memo {
+            f,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn call(&self, arg: A) -> B {
+        if let Some(result) = self.cache.borrow().get(&arg) {
+            return result.clone();
+        }
+
+        let result = (self.f)(&arg);
+        self.cache.borrow_mut().insert(arg, result.clone());
+        result
+    }
+}
+
+#[test]
+fn memo_runs_once_per_distinct_argument() {
+    use std::cell::Cell;
+
+    let calls = Cell::new(0);
+    let lengths = Memo::new(|word: &String| {
+        calls.set(calls.get() + 1);
+        word.len()
+    });
+
+    assert_eq!(lengths.call(String::from("hello")), 5);
+    assert_eq!(lengths.call(String::from("hi")), 2);
+    assert_eq!(lengths.call(String::from("hello")), 5);
+    assert_eq!(lengths.call(String::from("hi")), 2);
+    assert_eq!(calls.get(), 2);
+}
+
 // Capturing the Environment with Closures
 // In the workout generator example, we only used closures as inline anonymous functions. However, closures have an additional
 // capability that functions don’t have: they can capture their environment and access variables from the scope in which they’re
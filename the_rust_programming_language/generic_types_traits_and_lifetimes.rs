@@ -180,266 +180,2635 @@ fn main() {
 // operations that are available only for floating point types.
 impl Point<f32> {
     fn distance_from_origin(&self) -> f32 {
-        (self.x.powi(2), self.y.powi(2)).sqrt()
+        (self.x.powi(2) + self.y.powi(2)).sqrt()
     }
 }
 
-// Generic type parameters in a struct definition aren’t always the same as those you use in that struct’s method signatures. For
-// example, below defines the method mixup on the Point<T, U> struct from before. The method takes another Point as a parameter,
-// which might have different types from the self Point we’re calling mixup on. The method creates a new Point instance with the
-// x value from the self Point (of type T) and the y value from the passed-in Point (of type W).
-struct Point<T, U> {
-    x: T,
-    y: U,
+// Every call to distance_from_origin recomputes the sqrt. If we read it often, we can wrap the point and cache the result in a
+// Cell, which lets distance_from_origin fill the cache through &self (interior mutability, covered properly in Chapter 15). The
+// point itself is only changed through set, which clears the cache so the next read recomputes. A small Cacheable trait names
+// the two operations any such cache needs:
+use std::cell::Cell;
+
+trait Cacheable {
+    fn is_cached(&self) -> bool;
+    fn invalidate(&self);
 }
 
-impl<T, U> Point<T, U> {
-    fn mixup<V, W>(self, other: Point<V, W>) -> Point<T, W> {
+struct CachedPoint {
+    point: Point<f32>,
+    cache: Cell<Option<f32>>,
+}
+
+impl CachedPoint {
+    fn new(point: Point<f32>) -> CachedPoint {
+        CachedPoint {
+            point,
+            cache: Cell::new(None),
+        }
+    }
+
+    fn distance_from_origin(&self) -> f32 {
+        match self.cache.get() {
+            Some(distance) => distance,
+            None => {
+                let distance = self.point.distance_from_origin();
+                self.cache.set(Some(distance));
+                distance
+            }
+        }
+    }
+
+    fn set(&mut self, point: Point<f32>) {
+        self.point = point;
+        self.invalidate();
+    }
+}
+
+impl Cacheable for CachedPoint {
+    fn is_cached(&self) -> bool {
+        self.cache.get().is_some()
+    }
+
+    fn invalidate(&self) {
+        self.cache.set(None);
+    }
+}
+
+#[test]
+fn distance_is_computed_once() {
+    let mut cached = CachedPoint::new(Point { x: 3.0, y: 4.0 });
+    assert!(!cached.is_cached());
+    assert_eq!(cached.distance_from_origin(), 5.0);
+    assert!(cached.is_cached());
+
+    // Changing the point behind the cache's back shows the second read never recomputed the sqrt.
+    cached.point = Point { x: 6.0, y: 8.0 };
+    assert_eq!(cached.distance_from_origin(), 5.0);
+
+    cached.set(Point { x: 6.0, y: 8.0 });
+    assert!(!cached.is_cached());
+    assert_eq!(cached.distance_from_origin(), 10.0);
+}
+
+// In the same way, an impl Point<i32> block can give integer points a fixed-size binary encoding for passing them to other
+// programs: x and then y, each as four little-endian bytes. from_le_bytes checks that it was handed exactly eight bytes
+// before splitting them back into the two coordinates:
+use std::convert::TryInto;
+
+impl Point<i32> {
+    fn to_le_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8);
+        bytes.extend_from_slice(&self.x.to_le_bytes());
+        bytes.extend_from_slice(&self.y.to_le_bytes());
+        bytes
+    }
+
+    fn from_le_bytes(bytes: &[u8]) -> Result<Point<i32>, String> {
+        if bytes.len() != 8 {
+            return Err(format!("expected 8 bytes for a point, got {}", bytes.len()));
+        }
+
+        let (x, y) = bytes.split_at(4);
+
+        Ok(Point {
+            x: i32::from_le_bytes(x.try_into().unwrap()),
+            y: i32::from_le_bytes(y.try_into().unwrap()),
+        })
+    }
+}
+
+#[test]
+fn point_round_trips_through_bytes() {
+    let point = Point { x: 5, y: -10 };
+    let bytes = point.to_le_bytes();
+    assert_eq!(bytes, vec![5, 0, 0, 0, 246, 255, 255, 255]);
+
+    let decoded = Point::from_le_bytes(&bytes).unwrap();
+    assert_eq!((decoded.x, decoded.y), (5, -10));
+}
+
+#[test]
+fn point_from_too_few_bytes() {
+    assert_eq!(
+        Point::from_le_bytes(&[1, 0, 0, 0]).err(),
+        Some(String::from("expected 8 bytes for a point, got 4"))
+    );
+}
+
+// Methods can also have their own trait bounds on T. clamp only needs to compare and copy coordinates, so it works for any
+// Point<T> where T is PartialOrd + Copy. Each coordinate is clamped on its own into the range given by min and max; if a
+// coordinate of min is greater than the matching coordinate of max, the range is empty and that coordinate of min is used.
+fn clamp_coordinate<T: PartialOrd + Copy>(value: T, min: T, max: T) -> T {
+    if min > max || value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
+
+impl<T: PartialOrd + Copy> Point<T> {
+    fn clamp(self, min: Point<T>, max: Point<T>) -> Point<T> {
+        Point {
+            x: clamp_coordinate(self.x, min.x, max.x),
+            y: clamp_coordinate(self.y, min.y, max.y),
+        }
+    }
+}
+
+#[test]
+fn clamp_a_point() {
+    let clamp = |x, y| {
+        let point = Point { x, y }.clamp(Point { x: 0, y: 0 }, Point { x: 10, y: 20 });
+        (point.x, point.y)
+    };
+
+    assert_eq!(clamp(5, 15), (5, 15));
+    assert_eq!(clamp(-3, -1), (0, 0));
+    assert_eq!(clamp(12, 25), (10, 20));
+}
+
+#[test]
+fn clamp_with_min_above_max() {
+    let point = Point { x: 5.0, y: 5.0 }.clamp(Point { x: 8.0, y: 0.0 }, Point { x: 2.0, y: 10.0 });
+
+    assert_eq!((point.x, point.y), (8.0, 5.0));
+}
+
+// A Point<T> can be displayed as (x, y) whenever T itself implements Display. Going the other way, FromStr lets us parse
+// that format back into a Point<i32> with str::parse. Each way the input can be wrong gets its own ParsePointError variant
+// so callers can tell a missing parenthesis from a coordinate that isn't a number:
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum ParsePointError {
+    MissingParens,
+    MissingComma,
+    InvalidCoordinate(ParseIntError),
+}
+
+impl fmt::Display for ParsePointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePointError::MissingParens => write!(f, "a point must be wrapped in parentheses"),
+            ParsePointError::MissingComma => write!(f, "a point's coordinates must be separated by a comma"),
+            ParsePointError::InvalidCoordinate(e) => write!(f, "invalid coordinate: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ParsePointError {}
+
+impl FromStr for Point<i32> {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .trim()
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or(ParsePointError::MissingParens)?;
+        let (x, y) = inner.split_once(',').ok_or(ParsePointError::MissingComma)?;
+
+        Ok(Point {
+            x: x.trim().parse().map_err(ParsePointError::InvalidCoordinate)?,
+            y: y.trim().parse().map_err(ParsePointError::InvalidCoordinate)?,
+        })
+    }
+}
+
+#[test]
+fn parse_a_displayed_point() {
+    let point = Point { x: 3, y: -7 };
+    assert_eq!(point.to_string(), "(3, -7)");
+
+    let parsed: Point<i32> = point.to_string().parse().unwrap();
+    assert_eq!((parsed.x, parsed.y), (3, -7));
+}
+
+#[test]
+fn parse_a_point_without_a_comma() {
+    assert_eq!("(3 -7)".parse::<Point<i32>>().err(), Some(ParsePointError::MissingComma));
+    assert_eq!("3, -7".parse::<Point<i32>>().err(), Some(ParsePointError::MissingParens));
+}
+
+#[test]
+fn parse_a_point_with_a_non_numeric_coordinate() {
+    let err = "(3, seven)".parse::<Point<i32>>().err().unwrap();
+
+    assert!(matches!(err, ParsePointError::InvalidCoordinate(_)));
+    assert_eq!(err.to_string(), "invalid coordinate: invalid digit found in string");
+}
+
+// Integer points make natural indices into a two-dimensional grid. Grid<T> stores its cells row by row in a single Vec<T>,
+// and index turns a Point<i32> into a position in that Vec, or None when the point falls outside the grid (including
+// negative coordinates):
+struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    fn index(&self, p: &Point<i32>) -> Option<usize> {
+        if p.x < 0 || p.y < 0 {
+            return None;
+        }
+
+        let (x, y) = (p.x as usize, p.y as usize);
+
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    fn get(&self, p: Point<i32>) -> Option<&T> {
+        self.index(&p).map(|i| &self.cells[i])
+    }
+
+    fn set(&mut self, p: Point<i32>, value: T) -> Result<(), String> {
+        match self.index(&p) {
+            Some(i) => {
+                self.cells[i] = value;
+                Ok(())
+            }
+            None => Err(format!(
+                "{} is outside the {}x{} grid",
+                p, self.width, self.height
+            )),
+        }
+    }
+}
+
+#[test]
+fn set_and_get_inside_the_grid() {
+    let mut grid = Grid::new(3, 2, '.');
+
+    grid.set(Point { x: 2, y: 1 }, '#').unwrap();
+
+    assert_eq!(grid.get(Point { x: 2, y: 1 }), Some(&'#'));
+    assert_eq!(grid.get(Point { x: 1, y: 1 }), Some(&'.'));
+    assert_eq!(grid.cells, vec!['.', '.', '.', '.', '.', '#']);
+}
+
+#[test]
+fn access_outside_the_grid() {
+    let mut grid = Grid::new(3, 2, 0);
+
+    assert_eq!(grid.get(Point { x: 3, y: 0 }), None);
+    assert_eq!(grid.get(Point { x: -1, y: 0 }), None);
+    assert_eq!(
+        grid.set(Point { x: 0, y: 2 }, 1),
+        Err(String::from("(0, 2) is outside the 3x2 grid"))
+    );
+}
+
+// Quarter turns about the origin only ever swap and negate coordinates, so on integer points they stay exact with no
+// floating point rounding. rotate_cw turns (x, y) into (y, -x) and rotate_ccw undoes it by turning (x, y) into (-y, x).
+// The one value that can't be negated is i32::MIN, since i32::MAX is one smaller than its size. Plain - would panic on it
+// in a debug build, so both use wrapping_neg, which leaves i32::MIN as it is and keeps the two turns inverses of each other:
+impl Point<i32> {
+    fn rotate_cw(self) -> Point<i32> {
+        Point {
+            x: self.y,
+            y: self.x.wrapping_neg(),
+        }
+    }
+
+    fn rotate_ccw(self) -> Point<i32> {
+        Point {
+            x: self.y.wrapping_neg(),
+            y: self.x,
+        }
+    }
+}
+
+#[test]
+fn rotate_a_point_through_all_four_orientations() {
+    let coordinates = |point: &Point<i32>| (point.x, point.y);
+
+    let down = Point { x: 1, y: 0 }.rotate_cw();
+    assert_eq!(coordinates(&down), (0, -1));
+    let left = down.rotate_cw();
+    assert_eq!(coordinates(&left), (-1, 0));
+    let up = left.rotate_cw();
+    assert_eq!(coordinates(&up), (0, 1));
+    let right = up.rotate_cw();
+    assert_eq!(coordinates(&right), (1, 0));
+
+    let up = right.rotate_ccw();
+    assert_eq!(coordinates(&up), (0, 1));
+    let left = up.rotate_ccw();
+    assert_eq!(coordinates(&left), (-1, 0));
+    let down = left.rotate_ccw();
+    assert_eq!(coordinates(&down), (0, -1));
+    assert_eq!(coordinates(&down.rotate_ccw()), (1, 0));
+}
+
+#[test]
+fn rotate_cw_then_ccw_is_the_identity() {
+    let point = Point { x: 3, y: -7 }.rotate_cw().rotate_ccw();
+
+    assert_eq!((point.x, point.y), (3, -7));
+}
+
+#[test]
+fn rotate_a_point_at_i32_min_without_overflowing() {
+    let point = Point { x: i32::MIN, y: 5 }.rotate_cw();
+    assert_eq!((point.x, point.y), (5, i32::MIN));
+
+    let point = point.rotate_ccw();
+    assert_eq!((point.x, point.y), (i32::MIN, 5));
+}
+
+// Reflections are just as exact. reflect_x mirrors a point across the x axis by negating y, reflect_y mirrors it across
+// the y axis by negating x, and reflect_origin negates both, which lands in the same place as two quarter turns. Like the
+// rotations they negate with wrapping_neg, so a coordinate of i32::MIN stays i32::MIN instead of overflowing:
+impl Point<i32> {
+    fn reflect_x(self) -> Point<i32> {
         Point {
             x: self.x,
-            y: other.y,
+            y: self.y.wrapping_neg(),
+        }
+    }
+
+    fn reflect_y(self) -> Point<i32> {
+        Point {
+            x: self.x.wrapping_neg(),
+            y: self.y,
+        }
+    }
+
+    fn reflect_origin(self) -> Point<i32> {
+        Point {
+            x: self.x.wrapping_neg(),
+            y: self.y.wrapping_neg(),
         }
     }
 }
 
-fn main() {
-    let p1 = Point { x: 5, y: 10.4 };
-    let p2 = Point { x: "Hello", y: 'c' };
-    let p3 = p1.mixup(p2);
+#[test]
+fn reflect_a_point_across_each_axis() {
+    let point = || Point { x: 3, y: 4 };
+    let coordinates = |point: Point<i32>| (point.x, point.y);
 
-    println!("p3.x = {}, p3.y = {}", p3.x, p3.y); // p3.x = 5, p3.y = c
+    assert_eq!(coordinates(point().reflect_x()), (3, -4));
+    assert_eq!(coordinates(point().reflect_y()), (-3, 4));
+    assert_eq!(coordinates(point().reflect_origin()), (-3, -4));
+    assert_eq!(coordinates(point().reflect_origin()), coordinates(point().rotate_cw().rotate_cw()));
+    assert_eq!(coordinates(point().reflect_x().reflect_y()), (-3, -4));
 }
-// The purpose of this example is to demonstrate a situation in which some generic parameters are declared with impl and some are
-// declared with the method definition. Here, the generic parameters T and U are declared after impl, because they go with the struct
-// definition. The generic parameters V and W are declared after fn mixup, because they’re only relevant to the method.
 
-// Performance of generic types -> You might be wondering whether there is a runtime cost when you’re using generic type parameters.
-// The good news is that Rust implements generics in such a way that your code doesn’t run any slower using generic types than it would
-// with concrete types.
+#[test]
+fn reflect_a_point_at_i32_min_without_overflowing() {
+    let point = || Point { x: i32::MIN, y: 2 };
+    let coordinates = |point: Point<i32>| (point.x, point.y);
 
-// Rust accomplishes this by performing monomorphization of the code that is using generics at compile time. Monomorphization is the process
-// of turning generic code into specific code by filling in the concrete types that are used when compiled.
+    assert_eq!(coordinates(point().reflect_x()), (i32::MIN, -2));
+    assert_eq!(coordinates(point().reflect_y()), (i32::MIN, 2));
+    assert_eq!(coordinates(point().reflect_origin()), (i32::MIN, -2));
+    assert_eq!(coordinates(point().reflect_origin()), coordinates(point().rotate_cw().rotate_cw()));
+}
 
-// In this process, the compiler does the opposite of the steps we used to create the generic function in Listing 10-5: the compiler looks at
-// all the places where generic code is called and generates code for the concrete types the generic code is called with. Let’s look at how
-// this works with an example that uses the standard library’s Option<T> enum:
-let integer = Some(5);
-let float = Some(5.0);
-// When Rust compiles this code, it performs monomorphization. During that process, the compiler reads the values that have been used in
-// Option<T> instances and identifies two kinds of Option<T>: one is i32 and the other is f64. As such, it expands the generic definition
-// of Option<T> into Option_i32 and Option_f64, thereby replacing the generic definition with the specific ones.
+// Adding two points with + on each coordinate would panic in a debug build if a sum went past what an i32 can hold, and
+// quietly wrap around in a release build (see the note on integer overflow in Chapter 3). saturating_add adds each
+// coordinate with i32::saturating_add instead, so a sum that's too big stops at i32::MAX and one that's too small stops
+// at i32::MIN:
+impl Point<i32> {
+    fn saturating_add(self, other: Point<i32>) -> Point<i32> {
+        Point {
+            x: self.x.saturating_add(other.x),
+            y: self.y.saturating_add(other.y),
+        }
+    }
+}
 
-// The monomorphized version of the code looks like the following. The generic Option<T> is replaced with the specific definitions created
-// by the compiler:
-enum Option_i32 {
-    Some(i32),
-    None,
+#[test]
+fn saturating_add_clamps_at_the_edges() {
+    let point = Point { x: i32::MAX - 1, y: i32::MIN + 1 }.saturating_add(Point { x: 5, y: -5 });
+
+    assert_eq!((point.x, point.y), (i32::MAX, i32::MIN));
 }
 
-enum Option_f64 {
-    Some(f64),
-    None,
+#[test]
+fn saturating_add_without_overflow() {
+    let point = Point { x: i32::MAX - 5, y: -3 }.saturating_add(Point { x: 5, y: 10 });
+
+    assert_eq!((point.x, point.y), (i32::MAX, 7));
 }
 
-fn main() {
-    let integer = Option_i32::Some(5);
-    let float = Option_f64::Some(5.0);
+// Points don't have one obvious order, so rather than implementing PartialOrd on Point we compare them by a key. For
+// integer points the squared distance from the origin is exact and orders points the same way the distance itself
+// would; it's worked out in i64 so squaring a large coordinate can't overflow. largest_point then works like the
+// reference-returning version of largest described later in this chapter: it keeps a reference to the farthest point
+// seen so far and only replaces it with one that is strictly farther, so the first of several tied points wins. An
+// empty slice has no largest point:
+impl Point<i32> {
+    fn squared_distance_from_origin(&self) -> i64 {
+        let x = i64::from(self.x);
+        let y = i64::from(self.y);
+
+        x * x + y * y
+    }
 }
 
-// Traits: Defining Shared Behavior
-// A trait tells the Rust compiler about functionality a particular type has and can share with other types. We can use traits to define
-// shared behavior in an abstract way. We can use trait bounds to specify that a generic can be any type that has certain behavior. Traits
-// are similar to a feature often called interfaces in other languages, although with some differences.
+fn largest_point(points: &[Point<i32>]) -> Option<&Point<i32>> {
+    let mut largest = points.first()?;
 
-// A type’s behavior consists of the methods we can call on that type. Different types share the same behavior if we can call the same methods
-// on all of those types. Trait definitions are a way to group method signatures together to define a set of behaviors necessary to accomplish
-// some purpose. For example, let’s say we have multiple structs that hold various kinds and amounts of text: a NewsArticle struct that holds
-// a news story filed in a particular location and a Tweet that can have at most 280 characters along with metadata that indicates whether it was
-// a new tweet, a retweet, or a reply to another tweet.
+    for point in points {
+        if point.squared_distance_from_origin() > largest.squared_distance_from_origin() {
+            largest = point;
+        }
+    }
 
-// We want to make a media aggregator library that can display summaries of data that might be stored in a NewsArticle or Tweet instance. To do
-// this, we need a summary from each type, and we need to request that summary by calling a summarize method on an instance. Listing 10-12 shows
-// the definition of a Summary trait that expresses this behavior.
-pub trait Summary {
-    fn summerize(&self) -> String;
+    Some(largest)
 }
 
-pub struct NewsArticle {
-    pub headline: String,
-    pub location: String,
-    pub author: String,
-    pub content: String,
+#[test]
+fn largest_point_is_farthest_from_the_origin() {
+    let points = vec![Point { x: 1, y: 1 }, Point { x: -3, y: 4 }, Point { x: 2, y: -2 }];
+    let largest = largest_point(&points).unwrap();
+
+    assert_eq!((largest.x, largest.y), (-3, 4));
+    assert!(largest_point(&[]).is_none());
+}
+
+#[test]
+fn largest_point_keeps_the_first_of_a_tie() {
+    let points = vec![Point { x: 0, y: 1 }, Point { x: 3, y: 4 }, Point { x: 5, y: 0 }, Point { x: 0, y: -5 }];
+    let largest = largest_point(&points).unwrap();
+
+    assert_eq!((largest.x, largest.y), (3, 4));
+}
+
+// The centroid of a set of points is the point in the middle of them: its x is the average of every x and its y is the
+// average of every y. It's worked out on Point<f64> since the average of whole numbers usually isn't a whole number.
+// An empty slice has no middle, so centroid returns None rather than dividing by zero:
+fn centroid(points: &[Point<f64>]) -> Option<Point<f64>> {
+    if points.is_empty() {
+        return None;
+    }
+
+    let count = points.len() as f64;
+    let (sum_x, sum_y) = points
+        .iter()
+        .fold((0.0, 0.0), |(x, y), point| (x + point.x, y + point.y));
+
+    Some(Point {
+        x: sum_x / count,
+        y: sum_y / count,
+    })
+}
+
+#[test]
+fn centroid_of_a_square_and_a_triangle() {
+    let square = vec![
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 4.0, y: 0.0 },
+        Point { x: 4.0, y: 4.0 },
+        Point { x: 0.0, y: 4.0 },
+    ];
+    let center = centroid(&square).unwrap();
+    assert_eq!((center.x, center.y), (2.0, 2.0));
+
+    let triangle = vec![Point { x: 0.0, y: 0.0 }, Point { x: 6.0, y: 0.0 }, Point { x: 0.0, y: -3.0 }];
+    let center = centroid(&triangle).unwrap();
+    assert_eq!((center.x, center.y), (2.0, -1.0));
+}
+
+#[test]
+fn centroid_of_no_points() {
+    assert!(centroid(&[]).is_none());
+}
+
+// Floating point arithmetic rounds, so two f64 points that should be the same after different calculations can be a tiny
+// bit apart, and comparing their coordinates with == would call them different. approx_eq treats two points as equal when
+// each coordinate is within epsilon of the other point's:
+impl Point<f64> {
+    fn approx_eq(&self, other: &Point<f64>, epsilon: f64) -> bool {
+        (self.x - other.x).abs() <= epsilon && (self.y - other.y).abs() <= epsilon
+    }
+}
+
+#[test]
+fn points_within_the_tolerance_are_equal() {
+    let rounded = Point { x: 0.1 + 0.2, y: 0.7 + 0.1 };
+    let exact = Point { x: 0.3, y: 0.8 };
+
+    assert!(rounded.x != exact.x && rounded.y != exact.y);
+    assert!(rounded.approx_eq(&exact, 1e-9));
+    assert!(Point { x: 1.0, y: 2.0 }.approx_eq(&Point { x: 1.05, y: 1.95 }, 0.1));
+}
+
+#[test]
+fn points_outside_the_tolerance_are_not_equal() {
+    let point = Point { x: 1.0, y: 2.0 };
+
+    assert!(!point.approx_eq(&Point { x: 1.2, y: 2.0 }, 0.1));
+    assert!(!point.approx_eq(&Point { x: 1.0, y: 1.8 }, 0.1));
+}
+
+// The same rounding makes f64 points awkward to print in a test: 0.1 + 0.2 displays as 0.30000000000000004. format! can
+// take the number of decimal places as an argument with {:.*}, so to_string_precision rounds both coordinates to digits
+// places and prints them as (x, y). Two points that are equal to that many places then print the same:
+impl Point<f64> {
+    fn to_string_precision(&self, digits: usize) -> String {
+        format!("({:.*}, {:.*})", digits, self.x, digits, self.y)
+    }
+}
+
+#[test]
+fn print_a_point_to_a_fixed_precision() {
+    let point = Point { x: 12.345678, y: -0.654321 };
+
+    assert_eq!(point.to_string_precision(0), "(12, -1)");
+    assert_eq!(point.to_string_precision(2), "(12.35, -0.65)");
+    assert_eq!(point.to_string_precision(4), "(12.3457, -0.6543)");
+    assert_eq!(Point { x: 0.1 + 0.2, y: 1.0 }.to_string_precision(4), "(0.3000, 1.0000)");
+}
+
+// Since both coordinates of a Point<T> have the same type, they can also be handed out together as an array. coords
+// borrows them and into_coords moves them out, x first. Implementing IntoIterator on top of into_coords lets a point be
+// used directly in a for loop or with adapters like map and sum:
+impl<T> Point<T> {
+    fn coords(&self) -> [&T; 2] {
+        [&self.x, &self.y]
+    }
+
+    fn into_coords(self) -> [T; 2] {
+        [self.x, self.y]
+    }
+}
+
+impl<T> IntoIterator for Point<T> {
+    type Item = T;
+    type IntoIter = std::array::IntoIter<T, 2>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(self.into_coords())
+    }
+}
+
+#[test]
+fn sum_the_coordinates_of_a_point() {
+    let point = Point { x: 3, y: 4 };
+
+    assert_eq!(point.coords(), [&3, &4]);
+    assert_eq!(point.into_iter().sum::<i32>(), 7);
+}
+
+#[test]
+fn map_over_owned_coordinates() {
+    let point = Point { x: String::from("left"), y: String::from("up") };
+    let mut lengths = Vec::new();
+
+    for coordinate in point {
+        lengths.push(coordinate.len());
+    }
+
+    assert_eq!(lengths, vec![4, 2]);
+    assert_eq!(Point { x: 1.5, y: 2.5 }.into_coords(), [1.5, 2.5]);
+}
+
+// fold_coords reduces a point to one value the way Iterator::fold does, but without consuming the point: it calls f with
+// init and a reference to x, then with that result and y. Summing, taking the larger coordinate, or anything else that
+// combines the two then only needs a different init and f:
+impl<T> Point<T> {
+    fn fold_coords<B, F: Fn(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.coords().iter().fold(init, |acc, coordinate| f(acc, coordinate))
+    }
+}
+
+#[test]
+fn sum_coordinates_with_fold() {
+    assert_eq!(Point { x: 3, y: 4 }.fold_coords(0, |sum, &c| sum + c), 7);
+    assert_eq!(Point { x: 1.5, y: -0.5 }.fold_coords(0.0, |sum, &c| sum + c), 1.0);
+}
+
+#[test]
+fn max_coordinate_with_fold() {
+    let point = Point { x: -3, y: 8 };
+
+    assert_eq!(point.fold_coords(i32::MIN, |max, &c| max.max(c)), 8);
+    assert_eq!(Point { x: 5, y: 2 }.fold_coords(i32::MIN, |max, &c| max.max(c)), 5);
+    assert_eq!(point.fold_coords(String::new(), |s, c| format!("{}({})", s, c)), "(-3)(8)");
+}
+
+// Treating integer points as 2D vectors from the origin gives two more products. dot multiplies matching coordinates and
+// adds them, and it's 0 exactly when the two vectors are perpendicular. cross is the z part of the 3D cross product: it's
+// positive when other is a counterclockwise turn from self, negative when it's clockwise, and 0 when they point along the
+// same line. Multiplying two i32 coordinates can go far past i32::MAX, so both work in i64, where every product fits. The
+// only sum that doesn't is the dot product of (i32::MIN, i32::MIN) with itself, one past i64::MAX, so dot saturates:
+impl Point<i32> {
+    fn dot(&self, other: &Point<i32>) -> i64 {
+        let x = i64::from(self.x) * i64::from(other.x);
+        let y = i64::from(self.y) * i64::from(other.y);
+
+        x.saturating_add(y)
+    }
+
+    fn cross(&self, other: &Point<i32>) -> i64 {
+        i64::from(self.x) * i64::from(other.y) - i64::from(self.y) * i64::from(other.x)
+    }
+}
+
+#[test]
+fn dot_product_of_points() {
+    let point = Point { x: 3, y: 4 };
+
+    assert_eq!(point.dot(&Point { x: 2, y: -1 }), 2);
+    assert_eq!(point.dot(&point), 25);
+    assert_eq!(point.dot(&Point { x: -4, y: 3 }), 0);
+}
+
+#[test]
+fn cross_product_sign_gives_the_turn() {
+    let east = Point { x: 1, y: 0 };
+
+    assert_eq!(east.cross(&Point { x: 0, y: 1 }), 1);
+    assert_eq!(east.cross(&Point { x: 0, y: -1 }), -1);
+    assert_eq!(east.cross(&Point { x: 3, y: 0 }), 0);
+    assert_eq!(Point { x: 2, y: 3 }.cross(&Point { x: 4, y: 5 }), -2);
+}
+
+#[test]
+fn dot_and_cross_of_large_points_dont_overflow() {
+    let max = Point { x: i32::MAX, y: i32::MAX };
+    let min = Point { x: i32::MIN, y: i32::MIN };
+    let square = i64::from(i32::MAX) * i64::from(i32::MAX);
+
+    assert_eq!(max.dot(&max), 2 * square);
+    assert_eq!(max.dot(&Point { x: i32::MAX, y: -i32::MAX }), 0);
+    assert_eq!(min.dot(&min), i64::MAX);
+    assert_eq!(Point { x: i32::MAX, y: 0 }.cross(&Point { x: 0, y: i32::MAX }), square);
+    assert_eq!(Point { x: i32::MIN, y: i32::MAX }.cross(&min), i64::MAX - i64::from(i32::MAX));
+}
+
+// lerp, short for linear interpolation, finds the point a fraction t of the way along the line from self to other: t of
+// 0.0 is self, 1.0 is other, and 0.5 is halfway between them. Animations step t from 0 to 1 to move something smoothly
+// from one point to another. t is clamped to that range, so lerp never overshoots past either end:
+impl Point<f64> {
+    fn lerp(&self, other: &Point<f64>, t: f64) -> Point<f64> {
+        let t = t.clamp(0.0, 1.0);
+
+        Point {
+            x: self.x + (other.x - self.x) * t,
+            y: self.y + (other.y - self.y) * t,
+        }
+    }
+}
+
+#[test]
+fn lerp_between_two_points() {
+    let start = Point { x: 0.0, y: 10.0 };
+    let end = Point { x: 4.0, y: -2.0 };
+
+    let at_start = start.lerp(&end, 0.0);
+    let halfway = start.lerp(&end, 0.5);
+    let at_end = start.lerp(&end, 1.0);
+
+    assert_eq!((at_start.x, at_start.y), (0.0, 10.0));
+    assert_eq!((halfway.x, halfway.y), (2.0, 4.0));
+    assert_eq!((at_end.x, at_end.y), (4.0, -2.0));
+}
+
+#[test]
+fn lerp_clamps_t() {
+    let start = Point { x: 0.0, y: 10.0 };
+    let end = Point { x: 4.0, y: -2.0 };
+
+    let before = start.lerp(&end, -1.0);
+    let after = start.lerp(&end, 3.0);
+
+    assert_eq!((before.x, before.y), (0.0, 10.0));
+    assert_eq!((after.x, after.y), (4.0, -2.0));
+}
+
+// On a grid of integer points, the squares next to a point are its neighbors. neighbors_4 gives the four that share an
+// edge with it, in the order up, down, left, right (up is y + 1, as on a graph), and neighbors_8 adds the four diagonal
+// ones after them, going clockwise from up-right. Pathfinding uses one set or the other depending on whether diagonal
+// moves are allowed. A point on the edge of what an i32 can hold has no neighbor past that edge, so each step is taken
+// with checked_add and the ones that would overflow are left out rather than wrapping to the far side of the grid:
+impl Point<i32> {
+    fn step(&self, dx: i32, dy: i32) -> Option<Point<i32>> {
+        Some(Point {
+            x: self.x.checked_add(dx)?,
+            y: self.y.checked_add(dy)?,
+        })
+    }
+
+    fn neighbors_4(&self) -> Vec<Point<i32>> {
+        [(0, 1), (0, -1), (-1, 0), (1, 0)]
+            .iter()
+            .filter_map(|&(dx, dy)| self.step(dx, dy))
+            .collect()
+    }
+
+    fn neighbors_8(&self) -> Vec<Point<i32>> {
+        let mut neighbors = self.neighbors_4();
+        neighbors.extend(
+            [(1, 1), (1, -1), (-1, -1), (-1, 1)]
+                .iter()
+                .filter_map(|&(dx, dy)| self.step(dx, dy)),
+        );
+        neighbors
+    }
+}
+
+#[test]
+fn four_neighbors_of_the_origin() {
+    let neighbors: Vec<(i32, i32)> = Point { x: 0, y: 0 }.neighbors_4().iter().map(|p| (p.x, p.y)).collect();
+
+    assert_eq!(neighbors, vec![(0, 1), (0, -1), (-1, 0), (1, 0)]);
+}
+
+#[test]
+fn eight_neighbors_of_the_origin() {
+    let neighbors: Vec<(i32, i32)> = Point { x: 0, y: 0 }.neighbors_8().iter().map(|p| (p.x, p.y)).collect();
+
+    assert_eq!(
+        neighbors,
+        vec![(0, 1), (0, -1), (-1, 0), (1, 0), (1, 1), (1, -1), (-1, -1), (-1, 1)]
+    );
+    assert!(!neighbors.contains(&(0, 0)));
+}
+
+#[test]
+fn neighbors_at_the_edge_of_i32() {
+    let coordinates = |neighbors: Vec<Point<i32>>| neighbors.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+
+    assert_eq!(
+        coordinates(Point { x: i32::MAX, y: 0 }.neighbors_4()),
+        vec![(i32::MAX, 1), (i32::MAX, -1), (i32::MAX - 1, 0)]
+    );
+    assert_eq!(
+        coordinates(Point { x: i32::MIN, y: i32::MAX }.neighbors_8()),
+        vec![(i32::MIN, i32::MAX - 1), (i32::MIN + 1, i32::MAX), (i32::MIN + 1, i32::MAX - 1)]
+    );
+}
+
+// On a grid where moves only go along the axes, the distance between two points is the number of steps across plus the
+// number of steps up or down, known as the Manhattan distance after its street grid. The difference between two i32s
+// can be bigger than an i32 holds, such as from i32::MIN to i32::MAX, so each difference is worked out in i64. Their sum
+// can still be too big for the u32 result at the very edges, so it saturates at u32::MAX instead of wrapping around:
+use std::convert::TryFrom;
+
+impl Point<i32> {
+    fn manhattan(&self, other: &Point<i32>) -> u32 {
+        let dx = (i64::from(self.x) - i64::from(other.x)).abs();
+        let dy = (i64::from(self.y) - i64::from(other.y)).abs();
+
+        u32::try_from(dx + dy).unwrap_or(u32::MAX)
+    }
+}
+
+#[test]
+fn manhattan_distance_between_points() {
+    let origin = Point { x: 0, y: 0 };
+
+    assert_eq!(origin.manhattan(&Point { x: 3, y: 4 }), 7);
+    assert_eq!(Point { x: -2, y: 5 }.manhattan(&Point { x: 3, y: -1 }), 11);
+    assert_eq!(Point { x: -4, y: -4 }.manhattan(&Point { x: -4, y: -4 }), 0);
+}
+
+#[test]
+fn manhattan_distance_across_the_whole_range() {
+    let low = Point { x: i32::MIN, y: 0 };
+    let high = Point { x: i32::MAX, y: 0 };
+
+    assert_eq!(low.manhattan(&high), u32::MAX);
+    assert_eq!(Point { x: i32::MIN, y: i32::MIN }.manhattan(&Point { x: i32::MAX, y: i32::MAX }), u32::MAX);
+}
+
+// The bounding box of some points is the smallest rectangle, with sides along the axes, that has all of them inside it.
+// It's described by two corners: the smallest x with the smallest y, and the largest x with the largest y. bounding_box
+// starts both corners at the first point and widens them one coordinate at a time, so the corners don't have to be any
+// of the points themselves. An empty slice has no box:
+fn bounding_box(points: &[Point<i32>]) -> Option<(Point<i32>, Point<i32>)> {
+    let first = points.first()?;
+    let mut min = Point { x: first.x, y: first.y };
+    let mut max = Point { x: first.x, y: first.y };
+
+    for point in points {
+        min.x = min.x.min(point.x);
+        min.y = min.y.min(point.y);
+        max.x = max.x.max(point.x);
+        max.y = max.y.max(point.y);
+    }
+
+    Some((min, max))
+}
+
+#[test]
+fn bounding_box_of_scattered_points() {
+    let points = vec![Point { x: 3, y: -1 }, Point { x: -2, y: 4 }, Point { x: 5, y: 2 }, Point { x: 0, y: -6 }];
+    let (min, max) = bounding_box(&points).unwrap();
+
+    assert_eq!((min.x, min.y), (-2, -6));
+    assert_eq!((max.x, max.y), (5, 4));
+}
+
+#[test]
+fn bounding_box_of_one_point_and_of_none() {
+    let (min, max) = bounding_box(&[Point { x: 7, y: -7 }]).unwrap();
+
+    assert_eq!((min.x, min.y, max.x, max.y), (7, -7, 7, -7));
+    assert!(bounding_box(&[]).is_none());
+}
+
+// Generic type parameters in a struct definition aren’t always the same as those you use in that struct’s method signatures. For
+// example, below defines the method mixup on the Point<T, U> struct from before. The method takes another Point as a parameter,
+// which might have different types from the self Point we’re calling mixup on. The method creates a new Point instance with the
+// x value from the self Point (of type T) and the y value from the passed-in Point (of type W).
+struct Point<T, U> {
+    x: T,
+    y: U,
+}
+
+impl<T, U> Point<T, U> {
+    fn mixup<V, W>(self, other: Point<V, W>) -> Point<T, W> {
+        Point {
+            x: self.x,
+            y: other.y,
+        }
+    }
+}
+
+fn main() {
+    let p1 = Point { x: 5, y: 10.4 };
+    let p2 = Point { x: "Hello", y: 'c' };
+    let p3 = p1.mixup(p2);
+
+    println!("p3.x = {}, p3.y = {}", p3.x, p3.y); // p3.x = 5, p3.y = c
+}
+// The purpose of this example is to demonstrate a situation in which some generic parameters are declared with impl and some are
+// declared with the method definition. Here, the generic parameters T and U are declared after impl, because they go with the struct
+// definition. The generic parameters V and W are declared after fn mixup, because they’re only relevant to the method.
+
+// mixup picks one coordinate from each point. zip_with generalizes that: it combines the two x values with fx and the two y
+// values with gy, and because each closure can return its own type, the resulting point's types X and Y are declared on
+// the method alongside V and W. mixup is just the case where fx keeps the first x and gy keeps the second y.
+impl<T, U> Point<T, U> {
+    fn zip_with<V, W, X, Y, F: Fn(T, V) -> X, G: Fn(U, W) -> Y>(
+        self,
+        other: Point<V, W>,
+        fx: F,
+        gy: G,
+    ) -> Point<X, Y> {
+        Point {
+            x: fx(self.x, other.x),
+            y: gy(self.y, other.y),
+        }
+    }
+}
+
+#[test]
+fn zip_an_integer_point_with_a_float_point() {
+    let integer = Point { x: 5, y: 10 };
+    let float = Point { x: 1.5, y: 0.25 };
+
+    let sum = integer.zip_with(float, |a, b| a as f64 + b, |a, b| a as f64 * b);
+
+    assert_eq!((sum.x, sum.y), (6.5, 2.5));
+}
+
+// Methods can also rearrange the type parameters themselves. transpose swaps the two coordinates, and since x now holds
+// what used to be y, the returned point's types swap places too: a Point<T, U> comes back as a Point<U, T>.
+impl<T, U> Point<T, U> {
+    fn transpose(self) -> Point<U, T> {
+        Point {
+            x: self.y,
+            y: self.x,
+        }
+    }
+}
+
+#[test]
+fn transpose_a_point() {
+    let p = Point { x: 5, y: "a" };
+
+    let transposed: Point<&str, i32> = p.transpose();
+
+    assert_eq!((transposed.x, transposed.y), ("a", 5));
+}
+
+// Performance of generic types -> You might be wondering whether there is a runtime cost when you’re using generic type parameters.
+// The good news is that Rust implements generics in such a way that your code doesn’t run any slower using generic types than it would
+// with concrete types.
+
+// Rust accomplishes this by performing monomorphization of the code that is using generics at compile time. Monomorphization is the process
+// of turning generic code into specific code by filling in the concrete types that are used when compiled.
+
+// In this process, the compiler does the opposite of the steps we used to create the generic function in Listing 10-5: the compiler looks at
+// all the places where generic code is called and generates code for the concrete types the generic code is called with. Let’s look at how
+// this works with an example that uses the standard library’s Option<T> enum:
+let integer = Some(5);
+let float = Some(5.0);
+// When Rust compiles this code, it performs monomorphization. During that process, the compiler reads the values that have been used in
+// Option<T> instances and identifies two kinds of Option<T>: one is i32 and the other is f64. As such, it expands the generic definition
+// of Option<T> into Option_i32 and Option_f64, thereby replacing the generic definition with the specific ones.
+
+// The monomorphized version of the code looks like the following. The generic Option<T> is replaced with the specific definitions created
+// by the compiler:
+enum Option_i32 {
+    Some(i32),
+    None,
+}
+
+enum Option_f64 {
+    Some(f64),
+    None,
+}
+
+fn main() {
+    let integer = Option_i32::Some(5);
+    let float = Option_f64::Some(5.0);
+}
+
+// Traits: Defining Shared Behavior
+// A trait tells the Rust compiler about functionality a particular type has and can share with other types. We can use traits to define
+// shared behavior in an abstract way. We can use trait bounds to specify that a generic can be any type that has certain behavior. Traits
+// are similar to a feature often called interfaces in other languages, although with some differences.
+
+// A type’s behavior consists of the methods we can call on that type. Different types share the same behavior if we can call the same methods
+// on all of those types. Trait definitions are a way to group method signatures together to define a set of behaviors necessary to accomplish
+// some purpose. For example, let’s say we have multiple structs that hold various kinds and amounts of text: a NewsArticle struct that holds
+// a news story filed in a particular location and a Tweet that can have at most 280 characters along with metadata that indicates whether it was
+// a new tweet, a retweet, or a reply to another tweet.
+
+// We want to make a media aggregator library that can display summaries of data that might be stored in a NewsArticle or Tweet instance. To do
+// this, we need a summary from each type, and we need to request that summary by calling a summarize method on an instance. Listing 10-12 shows
+// the definition of a Summary trait that expresses this behavior.
+pub trait Summary {
+    fn summarize(&self) -> String;
+    // Appends the summary to buf, so callers in hot loops can reuse one String. The default still allocates through
+    // summarize; types that are summarized often can override it to write straight into the buffer.
+    fn summarize_into(&self, buf: &mut String) {
+        buf.push_str(&self.summarize());
+    }
+    // Who wrote the item, so summaries from the same person can be grouped together
+    fn summarize_author(&self) -> String;
+}
+
+pub struct NewsArticle {
+    pub headline: String,
+    pub location: String,
+    pub author: String,
+    pub content: String,
+}
+
+impl Summary for NewsArticle {
+    fn summarize(&self) -> String {
+        format!("{}, by {} ({})", self.headline, self.author, self.location)
+    }
+
+    fn summarize_author(&self) -> String {
+        self.author.clone()
+    }
+}
+
+pub struct Tweet {
+    pub username: String,
+    pub content: String,
+    pub reply: bool,
+    pub retweet: bool,
+}
+
+impl Summary for Tweet {
+    fn summarize(&self) -> String {
+        format!("{}: {}", self.username, self.content)
+    }
+
+    fn summarize_author(&self) -> String {
+        format!("@{}", self.username)
+    }
+}
+// Implementing a trait on a type is similar to implementing regular methods. The difference is that after impl, we put the trait name that we
+// want to implement, then use the for keyword, and then specify the name of the type we want to implement the trait for. Within the impl block,
+// we put the method signatures that the trait definition has defined. Instead of adding a semicolon after each signature, we use curly brackets
+// and fill in the method body with the specific behavior that we want the methods of the trait to have for the particular type.
+
+// Example showing how the above would be called (no different then a normal method):
+let tweet = Tweet {
+  username: String::from("horse_ebooks"),
+  content: String::from("of course, as you probably already know, people"),
+  reply: false,
+  retweet: false,
+};
+
+println!("1 new tweet: {}", tweet.summarize());
+
+// One restriction to note with trait implementations is that we can implement a trait on a type only if either the trait or the type is local to
+// our crate. For example, we can implement standard library traits like Display on a custom type like Tweet as part of our aggregator crate
+// functionality, because the type Tweet is local to our aggregator crate. We can also implement Summary on Vec<T> in our aggregator crate, because
+// the trait Summary is local to our aggregator crate.
+
+// But we can’t implement external traits on external types. For example, we can’t implement the Display trait on Vec<T> within our aggregator crate,
+// because Display and Vec<T> are defined in the standard library and aren’t local to our aggregator crate. This restriction is part of a property of
+// programs called coherence, and more specifically the orphan rule, so named because the parent type is not present. This rule ensures that other
+// people’s code can’t break your code and vice versa. Without the rule, two crates could implement the same trait for the same type, and Rust wouldn’t
+// know which implementation to use.
+
+// Building a NewsArticle means spelling out all four String fields at once. A builder lets us set them one at a time with chained calls and
+// only checks that everything was provided when we call build, reporting every missing field instead of just the first:
+#[derive(Default)]
+pub struct NewsArticleBuilder {
+    headline: Option<String>,
+    location: Option<String>,
+    author: Option<String>,
+    content: Option<String>,
+}
+
+impl NewsArticleBuilder {
+    pub fn new() -> NewsArticleBuilder {
+        NewsArticleBuilder::default()
+    }
+
+    pub fn headline(mut self, headline: &str) -> NewsArticleBuilder {
+        self.headline = Some(String::from(headline));
+        self
+    }
+
+    pub fn location(mut self, location: &str) -> NewsArticleBuilder {
+        self.location = Some(String::from(location));
+        self
+    }
+
+    pub fn author(mut self, author: &str) -> NewsArticleBuilder {
+        self.author = Some(String::from(author));
+        self
+    }
+
+    pub fn content(mut self, content: &str) -> NewsArticleBuilder {
+        self.content = Some(String::from(content));
+        self
+    }
+
+    pub fn build(self) -> Result<NewsArticle, String> {
+        match (self.headline, self.location, self.author, self.content) {
+            (Some(headline), Some(location), Some(author), Some(content)) => Ok(NewsArticle {
+                headline,
+                location,
+                author,
+                content,
+            }),
+            (headline, location, author, content) => {
+                let missing: Vec<&str> = vec![
+                    ("headline", headline.is_none()),
+                    ("location", location.is_none()),
+                    ("author", author.is_none()),
+                    ("content", content.is_none()),
+                ]
+                .into_iter()
+                .filter(|&(_, is_missing)| is_missing)
+                .map(|(name, _)| name)
+                .collect();
+
+                Err(format!("missing fields: {}", missing.join(", ")))
+            }
+        }
+    }
+}
+
+#[test]
+fn builds_a_complete_article() {
+    let article = NewsArticleBuilder::new()
+        .headline("Penguins win the Stanley Cup Championship!")
+        .location("Pittsburgh, PA, USA")
+        .author("Iceburgh")
+        .content("The Pittsburgh Penguins once again are the best hockey team in the NHL.")
+        .build()
+        .unwrap();
+
+    assert_eq!(article.author, "Iceburgh");
+    assert_eq!(article.location, "Pittsburgh, PA, USA");
+}
+
+#[test]
+fn build_reports_missing_author() {
+    let result = NewsArticleBuilder::new()
+        .headline("Penguins win the Stanley Cup Championship!")
+        .location("Pittsburgh, PA, USA")
+        .content("The Pittsburgh Penguins once again are the best hockey team in the NHL.")
+        .build();
+
+    assert_eq!(result.err(), Some(String::from("missing fields: author")));
+}
+
+// We said a Tweet holds at most 280 characters, but nothing stops us from building a longer one. validate checks the limit (counting
+// chars rather than bytes so emoji and accents count once), and Tweet::new runs it up front so a checked tweet can't be created too long:
+const MAX_TWEET_LEN: usize = 280;
+
+impl Tweet {
+    pub fn new(username: &str, content: &str, reply: bool, retweet: bool) -> Result<Tweet, String> {
+        let tweet = Tweet {
+            username: String::from(username),
+            content: String::from(content),
+            reply,
+            retweet,
+        };
+        tweet.validate()?;
+        Ok(tweet)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let len = self.content.chars().count();
+
+        if len > MAX_TWEET_LEN {
+            return Err(format!(
+                "tweet is over the {} character limit by {}",
+                MAX_TWEET_LEN,
+                len - MAX_TWEET_LEN
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn tweet(username: &str, content: &str) -> Tweet {
+    Tweet::new(username, content, false, false).unwrap()
+}
+
+#[test]
+fn tweet_at_the_limit_is_valid() {
+    assert!(Tweet::new("horse_ebooks", &"a".repeat(280), false, false).is_ok());
+}
+
+#[test]
+fn tweet_over_the_limit_reports_overage() {
+    let result = Tweet::new("horse_ebooks", &"a".repeat(281), false, false);
+
+    assert_eq!(
+        result.err(),
+        Some(String::from("tweet is over the 280 character limit by 1"))
+    );
+}
+
+#[test]
+fn short_tweet_is_valid() {
+    let tweet = Tweet::new("horse_ebooks", "of course, as you probably already know, people", false, false).unwrap();
+
+    assert!(tweet.validate().is_ok());
+}
+
+// Tweet::new also takes reply and retweet as two separate bools, so nothing stops a caller from passing true for both, even
+// though a tweet is either a reply to someone or a retweet of something, not both. TweetBuilder follows NewsArticleBuilder:
+// the setters chain, and build checks for the conflict before handing the fields to Tweet::new, which still checks the length.
+// The as_ setters borrow the builder instead of consuming it, so build borrows it too and copies the strings out:
+#[derive(Default)]
+pub struct TweetBuilder {
+    username: String,
+    content: String,
+    reply: bool,
+    retweet: bool,
+}
+
+impl TweetBuilder {
+    pub fn new(username: &str, content: &str) -> TweetBuilder {
+        TweetBuilder {
+            username: String::from(username),
+            content: String::from(content),
+            ..TweetBuilder::default()
+        }
+    }
+
+    pub fn as_reply(&mut self) -> &mut TweetBuilder {
+        self.reply = true;
+        self
+    }
+
+    pub fn as_retweet(&mut self) -> &mut TweetBuilder {
+        self.retweet = true;
+        self
+    }
+
+    pub fn build(&self) -> Result<Tweet, String> {
+        if self.reply && self.retweet {
+            return Err(String::from("a tweet can't be both a reply and a retweet"));
+        }
+
+        Tweet::new(&self.username, &self.content, self.reply, self.retweet)
+    }
+}
+
+#[test]
+fn builds_a_reply() {
+    let tweet = TweetBuilder::new("penguins", "@iceburgh thanks!").as_reply().build().unwrap();
+
+    assert!(tweet.reply);
+    assert!(!tweet.retweet);
+}
+
+#[test]
+fn builds_a_retweet() {
+    let tweet = TweetBuilder::new("iceburgh", "We did it!").as_retweet().build().unwrap();
+
+    assert!(!tweet.reply);
+    assert!(tweet.retweet);
+}
+
+#[test]
+fn build_rejects_a_reply_that_is_also_a_retweet() {
+    let result = TweetBuilder::new("penguins", "We did it!").as_reply().as_retweet().build();
+
+    assert_eq!(result.err(), Some(String::from("a tweet can't be both a reply and a retweet")));
+}
+
+// Fields are public, so a tweet can still grow past the limit after it's built. truncate_to_limit cuts it back down in
+// place and returns how many chars it removed, 0 if it already fit. String::truncate takes a byte index and panics if
+// that index falls inside a multibyte char, so we find the byte where the 281st char starts with char_indices and cut
+// there, which is always between two chars:
+impl Tweet {
+    pub fn truncate_to_limit(&mut self) -> usize {
+        match self.content.char_indices().nth(MAX_TWEET_LEN) {
+            Some((cut, _)) => {
+                let removed = self.content[cut..].chars().count();
+                self.content.truncate(cut);
+                removed
+            }
+            None => 0,
+        }
+    }
+}
+
+#[test]
+fn truncate_multibyte_content_to_the_limit() {
+    let mut tweet = Tweet {
+        username: String::from("penguins"),
+        content: "🐧é".repeat(150),
+        reply: false,
+        retweet: false,
+    };
+
+    assert_eq!(tweet.truncate_to_limit(), 20);
+    assert_eq!(tweet.content.chars().count(), 280);
+    assert!(tweet.content.ends_with("🐧é"));
+    assert!(tweet.validate().is_ok());
+    assert_eq!(tweet.truncate_to_limit(), 0);
+}
+
+#[test]
+fn truncate_leaves_short_content_alone() {
+    let mut tweet = Tweet {
+        username: String::from("penguins"),
+        content: String::from("Café ouvert!"),
+        reply: false,
+        retweet: false,
+    };
+
+    assert_eq!(tweet.truncate_to_limit(), 0);
+    assert_eq!(tweet.content, "Café ouvert!");
+}
+
+// One trait can be implemented for several types just like Summary is. Here a ToJson trait gives both types a lightweight JSON
+// form without pulling in a serialization crate. Field values are escaped by hand so content containing a " or a \ still produces
+// valid JSON:
+pub trait ToJson {
+    fn to_json(&self) -> String;
+}
+
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+impl ToJson for NewsArticle {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"headline\":\"{}\",\"location\":\"{}\",\"author\":\"{}\",\"content\":\"{}\"}}",
+            escape_json(&self.headline),
+            escape_json(&self.location),
+            escape_json(&self.author),
+            escape_json(&self.content)
+        )
+    }
+}
+
+impl ToJson for Tweet {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"username\":\"{}\",\"content\":\"{}\",\"reply\":{},\"retweet\":{}}}",
+            escape_json(&self.username),
+            escape_json(&self.content),
+            self.reply,
+            self.retweet
+        )
+    }
+}
+
+#[test]
+fn tweet_to_json_escapes_quotes() {
+    let tweet = Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("she said \"hi\""),
+        reply: false,
+        retweet: true,
+    };
+
+    assert_eq!(
+        tweet.to_json(),
+        r#"{"username":"horse_ebooks","content":"she said \"hi\"","reply":false,"retweet":true}"#
+    );
+}
+
+#[test]
+fn article_to_json_escapes_backslashes() {
+    let article = NewsArticle {
+        headline: String::from("C:\\ drive full"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+        content: String::from("ok"),
+    };
+
+    assert_eq!(
+        article.to_json(),
+        r#"{"headline":"C:\\ drive full","location":"Pittsburgh, PA, USA","author":"Iceburgh","content":"ok"}"#
+    );
+}
+
+// An aggregator showing its items on a web page needs HTML rather than JSON, so ToHtml follows the same shape. An article
+// becomes a heading and a paragraph and a tweet becomes a blockquote signed with the username. Text is escaped the same
+// way escape_json does it, except that for HTML the characters to replace are &, < and >, so a tweet containing a
+// <script> tag shows up as text instead of running:
+pub trait ToHtml {
+    fn to_html(&self) -> String;
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+impl ToHtml for NewsArticle {
+    fn to_html(&self) -> String {
+        format!("<h1>{}</h1><p>{}</p>", escape_html(&self.headline), escape_html(&self.content))
+    }
+}
+
+impl ToHtml for Tweet {
+    fn to_html(&self) -> String {
+        format!(
+            "<blockquote>{} &mdash; @{}</blockquote>",
+            escape_html(&self.content),
+            escape_html(&self.username)
+        )
+    }
+}
+
+#[test]
+fn tweet_to_html_escapes_a_script_tag() {
+    let tweet = Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("<script>alert(1)</script>"),
+        reply: false,
+        retweet: false,
+    };
+
+    assert_eq!(
+        tweet.to_html(),
+        "<blockquote>&lt;script&gt;alert(1)&lt;/script&gt; &mdash; @horse_ebooks</blockquote>"
+    );
+}
+
+#[test]
+fn article_to_html_escapes_ampersands_and_tags() {
+    let article = NewsArticle {
+        headline: String::from("Penguins & Pirates win"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+        content: String::from("Fans chanted <script>go</script>"),
+    };
+
+    assert_eq!(
+        article.to_html(),
+        "<h1>Penguins &amp; Pirates win</h1><p>Fans chanted &lt;script&gt;go&lt;/script&gt;</p>"
+    );
+}
+
+// Standard library traits can give our types an ordering, too, which is what a BinaryHeap needs to act as a priority queue.
+// Tweets are ordered by how long their content is in characters, with ties broken by username. This is an ordering by
+// length, not by recency or anything else. Eq has to agree with Ord, and two tweets should only be equal when every field
+// matches, so the rest of the fields come last in the key: they only decide the order of tweets whose lengths and
+// usernames are the same:
+use std::cmp::Ordering;
+
+impl Tweet {
+    fn ordering_key(&self) -> (usize, &str, &str, bool, bool) {
+        (self.content.chars().count(), &self.username, &self.content, self.reply, self.retweet)
+    }
+}
+
+impl PartialEq for Tweet {
+    fn eq(&self, other: &Tweet) -> bool {
+        self.ordering_key() == other.ordering_key()
+    }
+}
+
+impl Eq for Tweet {}
+
+impl PartialOrd for Tweet {
+    fn partial_cmp(&self, other: &Tweet) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Tweet {
+    fn cmp(&self, other: &Tweet) -> Ordering {
+        self.ordering_key().cmp(&other.ordering_key())
+    }
+}
+
+#[test]
+fn heap_of_tweets_pops_the_longest_first() {
+    use std::collections::BinaryHeap;
+
+    let mut heap = BinaryHeap::new();
+    heap.push(tweet("rustlang", "1.0 is out!"));
+    heap.push(tweet("horse_ebooks", "of course, as you probably already know, people"));
+    heap.push(tweet("bob", "hi"));
+    heap.push(tweet("alice", "hi"));
+
+    let order: Vec<String> = std::iter::from_fn(|| heap.pop()).map(|t| t.username).collect();
+
+    assert_eq!(order, vec!["horse_ebooks", "rustlang", "bob", "alice"]);
+}
+
+// With equality on every field, a feed can be deduplicated by putting it in a HashSet, which also needs Hash. Hash has to
+// agree with Eq, so values that are equal must hash the same; hashing exactly the fields that eq compares guarantees it.
+// NewsArticle gets the same treatment, comparing and hashing all four of its fields:
+use std::hash::{Hash, Hasher};
+
+impl Hash for Tweet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.username.hash(state);
+        self.content.hash(state);
+        self.reply.hash(state);
+        self.retweet.hash(state);
+    }
+}
+
+impl PartialEq for NewsArticle {
+    fn eq(&self, other: &NewsArticle) -> bool {
+        self.headline == other.headline
+            && self.location == other.location
+            && self.author == other.author
+            && self.content == other.content
+    }
+}
+
+impl Eq for NewsArticle {}
+
+impl Hash for NewsArticle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.headline.hash(state);
+        self.location.hash(state);
+        self.author.hash(state);
+        self.content.hash(state);
+    }
+}
+
+#[test]
+fn duplicate_tweets_collapse_in_a_hash_set() {
+    use std::collections::HashSet;
+
+    let mut feed = HashSet::new();
+    feed.insert(tweet("penguins", "We did it!"));
+    feed.insert(tweet("penguins", "We did it!"));
+    feed.insert(Tweet { retweet: true, ..tweet("penguins", "We did it!") });
+    feed.insert(tweet("penguins", "We won it!"));
+    feed.insert(tweet("iceburgh", "We did it!"));
+
+    assert_eq!(feed.len(), 4);
+    assert!(feed.contains(&Tweet { retweet: true, ..tweet("penguins", "We did it!") }));
+}
+
+#[test]
+fn duplicate_articles_collapse_in_a_hash_set() {
+    use std::collections::HashSet;
+
+    let article = |headline: &str| NewsArticle {
+        headline: String::from(headline),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+        content: String::from("The Pittsburgh Penguins once again are the best hockey team in the NHL."),
+    };
+    let articles: HashSet<NewsArticle> = vec![
+        article("Penguins win the Stanley Cup Championship!"),
+        article("Parade on Wednesday"),
+        article("Penguins win the Stanley Cup Championship!"),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(articles.len(), 2);
+}
+
+// A tweet mentions someone by writing @ followed by their handle, which is made of letters, digits, and underscores.
+// mentions walks the content one char at a time and reads a handle after every @, so punctuation right after it, like
+// the comma in "@bob, hi", ends the handle instead of becoming part of it. An @ with no handle after it isn't a mention,
+// and neither is one stuck to the end of a word, like the one in an email address:
+impl Tweet {
+    pub fn mentions(&self) -> Vec<String> {
+        words_after(&self.content, '@')
+    }
+}
+
+// Reads the word of letters, digits, and underscores that follows each marker in content that isn't stuck to the end of
+// another word
+fn words_after(content: &str, marker: char) -> Vec<String> {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut words = Vec::new();
+    let mut chars = content.chars().peekable();
+    let mut previous = None;
+
+    while let Some(c) = chars.next() {
+        if c == marker && !previous.is_some_and(is_word_char) {
+            let mut word = String::new();
+            while let Some(&next) = chars.peek() {
+                if !is_word_char(next) {
+                    break;
+                }
+                word.push(next);
+                chars.next();
+            }
+
+            if !word.is_empty() {
+                previous = word.chars().last();
+                words.push(word);
+                continue;
+            }
+        }
+        previous = Some(c);
+    }
+
+    words
+}
+
+#[test]
+fn mentions_in_a_tweet() {
+    assert_eq!(tweet("penguins", "Thanks @iceburgh and @pittsburgh_pa for a great season").mentions(), vec!["iceburgh", "pittsburgh_pa"]);
+    assert_eq!(tweet("penguins", "(@bob), @alice! Are you coming, @carol?").mentions(), vec!["bob", "alice", "carol"]);
+    assert_eq!(tweet("penguins", "Meet @ the rink, or email tickets@example.com").mentions(), Vec::<String>::new());
+    assert!(tweet("penguins", "We did it!").mentions().is_empty());
+}
+
+// Hashtags are read the same way as mentions, just after a # instead of an @. hashtags also lowercases every tag, since
+// #Rust and #rust are the same tag, and keeps only the first time each one shows up so a tweet that repeats a tag lists
+// it once, in the order the tags first appeared:
+impl Tweet {
+    pub fn hashtags(&self) -> Vec<String> {
+        let mut hashtags: Vec<String> = Vec::new();
+
+        for tag in words_after(&self.content, '#') {
+            let tag = tag.to_lowercase();
+            if !hashtags.contains(&tag) {
+                hashtags.push(tag);
+            }
+        }
+
+        hashtags
+    }
+}
+
+#[test]
+fn hashtags_in_a_tweet() {
+    assert_eq!(tweet("penguins", "Back to back! #LetsGoPens #StanleyCup").hashtags(), vec!["letsgopens", "stanleycup"]);
+    assert_eq!(tweet("penguins", "Parade on Wednesday (#Pittsburgh). #champions!").hashtags(), vec!["pittsburgh", "champions"]);
+    assert_eq!(tweet("penguins", "#Pens win! #pens #StanleyCup #PENS").hashtags(), vec!["pens", "stanleycup"]);
+    assert!(tweet("penguins", "We're # 1, and C# isn't a tag").hashtags().is_empty());
+}
+
+// Pasted content often comes with stray tabs, line breaks, or doubled spaces. sanitized_content cleans that up for display:
+// split_whitespace splits on any run of whitespace and skips it at both ends, so joining the pieces with single spaces
+// collapses every run to one space and trims the result. It returns a new String and leaves the tweet as it was:
+impl Tweet {
+    pub fn sanitized_content(&self) -> String {
+        self.content.split_whitespace().collect::<Vec<&str>>().join(" ")
+    }
+}
+
+#[test]
+fn sanitized_content_collapses_whitespace() {
+    assert_eq!(tweet("penguins", "We\tdid\t\tit!").sanitized_content(), "We did it!");
+    assert_eq!(tweet("penguins", "Back to back!\n\n#LetsGoPens\r\n").sanitized_content(), "Back to back! #LetsGoPens");
+    assert_eq!(tweet("penguins", "   See you  at the parade   ").sanitized_content(), "See you at the parade");
+    assert_eq!(tweet("penguins", " \t\n ").sanitized_content(), "");
+
+    let messy = tweet("penguins", "  We  did it! ");
+    messy.sanitized_content();
+    assert_eq!(messy.content, "  We  did it! ");
+}
+
+// Default implementations
+// Sometimes it’s useful to have default behavior for some or all of the methods in a trait instead of requiring implementations for all methods on every
+// type. Then, as we implement the trait on a particular type, we can keep or override each method’s default behavior.
+
+// The below example shows how to specify a default string for the summarize method of the Summary trait instead of only defining the method signature,
+// as we did in before.
+pub trait Summary {
+    fn summarize(&self) -> String {
+        String::from("(Read more...)");
+    }
+}
+// To use a default implementation to summarize instances of NewsArticle instead of defining a custom implementation, we specify an empty impl block with
+impl Summary for NewsArticle {}
+
+// Even though we’re no longer defining the summarize method on NewsArticle directly, we’ve provided a default implementation and specified that NewsArticle
+// implements the Summary trait. As a result, we can still call the summarize method on an instance of NewsArticle, like this:
+let article = NewsArticle {
+    headline: String::from("Penguins win the Stanley Cup Championship!"),
+    location: String::from("Pittsburgh, PA, USA"),
+    author: String::from("Iceburgh"),
+    content: String::from("The Pittsburgh Penguins once again are the best hockey team in the NHL."),
+};
+println!("New article available! {}", article.summarize());
+
+// Default implementations can call other methods in the same trait, even if those other methods don’t have a default implementation. In this way, a trait
+// can provide a lot of useful functionality and only require implementors to specify a small part of it. For example, we could define the Summary trait to
+// have a summarize_author method whose implementation is required, and then define a summarize method that has a default implementation that calls the
+// summarize_author method:
+pub trait Summary {
+    fn summarize_author(&self) -> String;
+    fn summarize(&self) -> String {
+        format!("(Read more from {}...)", self.summarize_author())
+    }
+}
+
+impl Summary for Tweet {
+    fn summarize_author(&self) -> String {
+        format!("@{}", self.username)
+    }
+}
+
+// After we define summarize_author, we can call summarize on instances of the Tweet struct, and the default implementation of summarize will call the
+// definition of summarize_author that we’ve provided. Because we’ve implemented summarize_author, the Summary trait has given us the behavior of the
+// summarize method without requiring us to write any more code.
+
+// Traits as Parameters
+// Previously, we implemented the Summary trait on the NewsArticle and Tweet types. We can define a notify function that calls the summarize method on its
+// item parameter, which is of some type that implements the Summary trait. To do this, we can use the impl Trait syntax, like this:
+pub fn notify(item: impl Summary) {
+    println!("Breaking news! {}", item.summarize());
+}
+// Instead of a concrete type for the item parameter, we specify the impl keyword and the trait name. This parameter accepts any type that implements the
+// specified trait. In the body of notify, we can call any methods on item that come from the Summary trait, such as summarize. We can call notify and pass
+// in any instance of NewsArticle or Tweet. Code that calls the function with any other type, such as a String or an i32, won’t compile because those types
+// don’t implement Summary.
+
+// The impl Trait syntax works for straightforward cases but is actually syntax sugar for a longer form, which is called a trait bound; it looks like this:
+pub fn notify<T: Summary>(item: T) {
+    println!("Breaking news! {}", item.summarize());
+}
+
+// The impl Trait syntax is convenient and makes for more concise code in simple cases. The trait bound syntax can express more complexity in other cases. For
+// example, we can have two parameters that implement Summary. Using the impl Trait syntax looks like this:
+pub fn notify(item1: impl Summary, item2: impl Summary) {}
+
+// If we wanted this function to allow item1 and item2 to have different types, using impl Trait would be appropriate (as long as both types implement Summary).
+// If we wanted to force both parameters to have the same type, that’s only possible to express using a trait bound, like this:
+pub fn notify<T: Summary>(item1: T, item2: T) {}
+
+// We can also specify more than one trait bound. Say we wanted notify to use display formatting on item as well as the summarize method: we specify in the notify
+// definition that item must implement both Display and Summary. We can do so using the + syntax:
+pub fn notify(item: impl Summary + Display) {}
+
+// Or
+pub fn notify<T: Summary + Display>(item: T) {}
+
+// Using too many trait bounds has its downsides. Each generic has its own trait bounds, so functions with multiple generic type parameters can contain lots of trait
+// bound information between the function’s name and its parameter list, making the function signature hard to read. For this reason, Rust has alternate syntax for
+// specifying trait bounds inside a where clause after the function signature. So instead of writing this:
+fn some_function<T: Display + Clone, U: Clone + Debug>(t: T, u: U) -> i32 {}
+
+// We can do this:
+fn some_function<T, U>(t: T, u: U) -> i32
+where
+    T: Display + Clone,
+    U: Clone + Debug,
+{
+    println!("function body");
+}
+
+// Returning Types that Implement Traits.
+// We can also use the impl Trait syntax in the return position to return a value of some type that implements a trait, as shown here:
+fn returns_summarizable() -> impl Summary {
+    Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: false,
+    }
+}
+// By using impl Summary for the return type, we specify that the returns_summarizable function returns some type that implements the Summary trait without naming
+// the concrete type. In this case, returns_summarizable returns a Tweet, but the code calling this function doesn’t know that. The ability to return a type that
+// is only specified by the trait it implements is especially useful in the context of closures and iterators, which we cover in Chapter 13. Closures and iterators
+// create types that only the compiler knows or types that are very long to specify. The impl Trait syntax lets you concisely specify that a function returns some
+// type that implements the Iterator trait without needing to write out a very long type.
+
+// However, you can only use impl Trait if you’re returning a single type. For example, this code that returns either a NewsArticle or a Tweet with the return type
+// specified as impl Summary wouldn’t work. Does not compile:
+fn returns_summarizable(switch: bool) -> impl Summary {
+    if switch {
+        NewsArticle {
+            headline: String::from("Penguins win the Stanley Cup Championship!"),
+            location: String::from("Pittsburgh, PA, USA"),
+            author: String::from("Iceburgh"),
+            content: String::from("The Pittsburgh Penguins once again are the best
+            hockey team in the NHL."),
+        }
+    } else {
+        Tweet {
+            username: String::from("horse_ebooks"),
+            content: String::from("of course, as you probably already know, people"),
+            reply: false,
+            retweet: false,
+        }
+    }
+}
+
+// The usual way around this is to name a single type that can hold either one. Either is an enum with a variant for each
+// side, and it implements Summary whenever both sides do by forwarding to whichever value it holds. returns_either can then
+// pick a NewsArticle or a Tweet at runtime, because both branches return the same type:
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L: Summary, R: Summary> Summary for Either<L, R> {
+    fn summarize(&self) -> String {
+        match self {
+            Either::Left(left) => left.summarize(),
+            Either::Right(right) => right.summarize(),
+        }
+    }
+
+    fn summarize_author(&self) -> String {
+        match self {
+            Either::Left(left) => left.summarize_author(),
+            Either::Right(right) => right.summarize_author(),
+        }
+    }
+}
+
+fn returns_either(switch: bool) -> Either<NewsArticle, Tweet> {
+    if switch {
+        Either::Left(NewsArticle {
+            headline: String::from("Penguins win the Stanley Cup Championship!"),
+            location: String::from("Pittsburgh, PA, USA"),
+            author: String::from("Iceburgh"),
+            content: String::from("The Pittsburgh Penguins once again are the best hockey team in the NHL."),
+        })
+    } else {
+        Either::Right(Tweet {
+            username: String::from("horse_ebooks"),
+            content: String::from("of course, as you probably already know, people"),
+            reply: false,
+            retweet: false,
+        })
+    }
+}
+
+#[test]
+fn either_summarizes_whichever_side_it_holds() {
+    let article = returns_either(true);
+    let tweet = returns_either(false);
+
+    assert_eq!(article.summarize(), "Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)");
+    assert_eq!(article.summarize_author(), "Iceburgh");
+    assert_eq!(tweet.summarize(), "horse_ebooks: of course, as you probably already know, people");
+    assert_eq!(tweet.summarize_author(), "@horse_ebooks");
+}
+
+// Because Summary is our own trait, we can also implement it for standard library types that wrap a summarizable value. These blanket
+// impls forward summarize through a Box<T> or a &T, so a Box<NewsArticle> or a &Tweet can be passed anywhere an impl Summary is expected
+// without dereferencing by hand. The ?Sized bound lets T be a trait object too, so Box<dyn Summary> gets the impl as well:
+impl<T: Summary + ?Sized> Summary for Box<T> {
+    fn summarize(&self) -> String {
+        (**self).summarize()
+    }
+    fn summarize_author(&self) -> String {
+        (**self).summarize_author()
+    }
+}
+
+impl<T: Summary + ?Sized> Summary for &T {
+    fn summarize(&self) -> String {
+        (**self).summarize()
+    }
+    fn summarize_author(&self) -> String {
+        (**self).summarize_author()
+    }
+}
+
+#[test]
+fn summarize_through_box_and_reference() {
+    let article = Box::new(NewsArticle {
+        headline: String::from("Penguins win the Stanley Cup Championship!"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+        content: String::from("The Pittsburgh Penguins once again are the best hockey team in the NHL."),
+    });
+    let tweet = Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: false,
+    };
+
+    fn summary_of(item: impl Summary) -> String {
+        item.summarize()
+    }
+
+    assert_eq!(
+        summary_of(article),
+        "Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)"
+    );
+    assert_eq!(
+        summary_of(&tweet),
+        "horse_ebooks: of course, as you probably already know, people"
+    );
+}
+
+// impl Summary only lets one concrete type through at a time. To hold articles and tweets side by side, which is what a media aggregator
+// actually needs, a Feed stores trait objects (covered properly in Chapter 17) and works with them only through summarize:
+pub struct Feed {
+    items: Vec<Box<dyn Summary>>,
+}
+
+impl Feed {
+    pub fn new() -> Feed {
+        Feed { items: Vec::new() }
+    }
+
+    pub fn push(&mut self, item: Box<dyn Summary>) {
+        self.items.push(item);
+    }
+
+    pub fn render(&self) -> String {
+        self.items
+            .iter()
+            .map(|item| item.summarize())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn longest(&self) -> Option<String> {
+        self.items
+            .iter()
+            .map(|item| item.summarize())
+            .max_by_key(|summary| summary.chars().count())
+    }
+}
+
+#[test]
+fn feed_renders_mixed_items() {
+    let mut feed = Feed::new();
+    feed.push(Box::new(NewsArticle {
+        headline: String::from("Penguins win the Stanley Cup Championship!"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+        content: String::from("The Pittsburgh Penguins once again are the best hockey team in the NHL."),
+    }));
+    feed.push(Box::new(Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: false,
+    }));
+    feed.push(Box::new(Tweet {
+        username: String::from("rustlang"),
+        content: String::from("1.0 is out!"),
+        reply: false,
+        retweet: true,
+    }));
+
+    assert_eq!(
+        feed.render(),
+        "Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)\n\
+         horse_ebooks: of course, as you probably already know, people\n\
+         rustlang: 1.0 is out!"
+    );
+    assert_eq!(
+        feed.longest(),
+        Some(String::from("Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)"))
+    );
+}
+
+// Since summarize_author is part of the Summary trait, a generic function can use it to build per-user feeds. group_by_author
+// takes ownership of the items and buckets them in a HashMap keyed by author, keeping each author's items in their original order:
+use std::collections::HashMap;
+
+pub fn group_by_author<T: Summary>(items: Vec<T>) -> HashMap<String, Vec<T>> {
+    let mut groups: HashMap<String, Vec<T>> = HashMap::new();
+
+    for item in items {
+        groups.entry(item.summarize_author()).or_default().push(item);
+    }
+
+    groups
+}
+
+#[test]
+fn group_tweets_by_author() {
+    let tweets = vec![
+        tweet("horse_ebooks", "of course, as you probably already know, people"),
+        tweet("rustlang", "1.0 is out!"),
+        tweet("horse_ebooks", "everything happens so much"),
+    ];
+
+    let groups = group_by_author(tweets);
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(
+        groups["@horse_ebooks"].iter().map(|t| t.content.as_str()).collect::<Vec<&str>>(),
+        vec!["of course, as you probably already know, people", "everything happens so much"]
+    );
+    assert_eq!(groups["@rustlang"].len(), 1);
+}
+
+// impl Trait also works inside other types, so notify_opt can take an item that might not be there without making callers
+// unwrap it first. The message is written to any io::Write so the output can be captured in a test, and notify_opt sends it
+// to stdout just like notify does:
+use std::io::{self, Write};
+
+pub fn write_notify_opt(out: &mut impl Write, item: Option<impl Summary>) -> io::Result<()> {
+    match item {
+        Some(item) => writeln!(out, "Breaking news! {}", item.summarize()),
+        None => writeln!(out, "No news."),
+    }
+}
+
+pub fn notify_opt(item: Option<impl Summary>) {
+    write_notify_opt(&mut io::stdout(), item).unwrap();
+}
+
+#[test]
+fn notify_opt_with_and_without_news() {
+    let tweet = Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: false,
+    };
+
+    let mut out = Vec::new();
+    write_notify_opt(&mut out, Some(tweet)).unwrap();
+    write_notify_opt(&mut out, None::<Tweet>).unwrap();
+
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "Breaking news! horse_ebooks: of course, as you probably already know, people\nNo news.\n"
+    );
+}
+
+// notify handles one item at a time. notify_stream takes any iterator of summarizable items, so the items can come from a
+// Vec, a filter over one, or anything else that produces them, and builds each "Breaking news!" message as the item comes
+// through. It returns the messages instead of printing them so callers, and tests, decide what to do with them:
+pub fn notify_stream<T: Summary, I: Iterator<Item = T>>(items: I) -> Vec<String> {
+    items
+        .map(|item| format!("Breaking news! {}", item.summarize()))
+        .collect()
+}
+
+#[test]
+fn notify_stream_of_filtered_tweets() {
+    let tweets = vec![
+        tweet("penguins", "We did it!"),
+        Tweet { retweet: true, ..tweet("iceburgh", "We did it!") },
+        tweet("rustlang", "1.0 is out!"),
+    ];
+
+    assert_eq!(
+        notify_stream(tweets.into_iter().filter(|tweet| !tweet.retweet)),
+        vec!["Breaking news! penguins: We did it!", "Breaking news! rustlang: 1.0 is out!"]
+    );
+    assert!(notify_stream(Vec::<Tweet>::new().into_iter()).is_empty());
+}
+
+// The orphan rule only requires that either the trait or the type is local, and Summary is ours, so we can implement it for a
+// standard library tuple too. A pair of summarizable items, like a tweet and the article it links to, summarizes as both
+// summaries side by side:
+impl<A: Summary, B: Summary> Summary for (A, B) {
+    fn summarize(&self) -> String {
+        format!("{} | {}", self.0.summarize(), self.1.summarize())
+    }
+
+    fn summarize_author(&self) -> String {
+        format!("{} | {}", self.0.summarize_author(), self.1.summarize_author())
+    }
+}
+
+#[test]
+fn summarize_a_tweet_and_article_pair() {
+    let pair = (
+        Tweet {
+            username: String::from("penguins"),
+            content: String::from("We did it!"),
+            reply: false,
+            retweet: false,
+        },
+        NewsArticle {
+            headline: String::from("Penguins win the Stanley Cup Championship!"),
+            location: String::from("Pittsburgh, PA, USA"),
+            author: String::from("Iceburgh"),
+            content: String::from("The Pittsburgh Penguins once again are the best hockey team in the NHL."),
+        },
+    );
+
+    assert_eq!(
+        pair.summarize(),
+        "penguins: We did it! | Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)"
+    );
+    assert_eq!(pair.summarize_author(), "@penguins | Iceburgh");
+}
+
+// Result is another standard library type we can implement Summary for. When both the success and the error values are
+// summarizable, the outcome of an operation summarizes as whichever one it holds, marked with OK: or ERR: so the two can
+// be told apart:
+impl<T: Summary, E: Summary> Summary for Result<T, E> {
+    fn summarize(&self) -> String {
+        match self {
+            Ok(value) => format!("OK: {}", value.summarize()),
+            Err(error) => format!("ERR: {}", error.summarize()),
+        }
+    }
+
+    fn summarize_author(&self) -> String {
+        match self {
+            Ok(value) => value.summarize_author(),
+            Err(error) => error.summarize_author(),
+        }
+    }
+}
+
+#[test]
+fn summarize_an_ok_result() {
+    let posted: Result<Tweet, NewsArticle> = Ok(Tweet {
+        username: String::from("penguins"),
+        content: String::from("We did it!"),
+        reply: false,
+        retweet: false,
+    });
+
+    assert_eq!(posted.summarize(), "OK: penguins: We did it!");
+    assert_eq!(posted.summarize_author(), "@penguins");
+}
+
+#[test]
+fn summarize_an_err_result() {
+    let posted: Result<Tweet, NewsArticle> = Err(NewsArticle {
+        headline: String::from("Tweet rejected for being too long"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+        content: String::from("The tweet went over the character limit."),
+    });
+
+    assert_eq!(posted.summarize(), "ERR: Tweet rejected for being too long, by Iceburgh (Pittsburgh, PA, USA)");
+    assert_eq!(posted.summarize_author(), "Iceburgh");
+}
+
+// Every Summary gets summarize_into, so a loop over many items can clear and reuse one String for the results rather than
+// collecting a new one from every summarize call:
+#[test]
+fn summarize_into_reuses_one_buffer() {
+    let tweets = vec![
+        Tweet {
+            username: String::from("horse_ebooks"),
+            content: String::from("of course, as you probably already know, people"),
+            reply: false,
+            retweet: false,
+        },
+        Tweet {
+            username: String::from("rustlang"),
+            content: String::from("1.0 is out!"),
+            reply: false,
+            retweet: false,
+        },
+    ];
+    let mut buf = String::with_capacity(64);
+    let mut summaries = Vec::new();
+
+    for tweet in &tweets {
+        buf.clear();
+        tweet.summarize_into(&mut buf);
+        summaries.push(buf.clone());
+    }
+
+    assert_eq!(
+        summaries,
+        vec![
+            "horse_ebooks: of course, as you probably already know, people",
+            "rustlang: 1.0 is out!",
+        ]
+    );
+    assert_eq!(tweets[1].summarize(), "rustlang: 1.0 is out!");
+}
+
+// If summarizing an article were expensive, a feed that renders often would want to do it only once. CachedArticle wraps a
+// summarizable item and keeps the first summary for summarize to hand back. CachedPoint could use a Cell because an f32 is
+// Copy, but a String isn't, so the summary sits in a RefCell and each call clones it out of a short borrow. It defaults to
+// wrapping a NewsArticle, and changing the article's content through set_content clears the cache:
+use std::cell::{Cell, RefCell};
+
+pub struct CachedArticle<S: Summary = NewsArticle> {
+    article: S,
+    cache: RefCell<Option<String>>,
+}
+
+impl<S: Summary> CachedArticle<S> {
+    pub fn new(article: S) -> CachedArticle<S> {
+        CachedArticle {
+            article,
+            cache: RefCell::new(None),
+        }
+    }
+}
+
+impl CachedArticle<NewsArticle> {
+    pub fn set_content(&mut self, content: &str) {
+        self.article.content = String::from(content);
+        *self.cache.get_mut() = None;
+    }
+}
+
+impl<S: Summary> Summary for CachedArticle<S> {
+    fn summarize(&self) -> String {
+        self.cache
+            .borrow_mut()
+            .get_or_insert_with(|| self.article.summarize())
+            .clone()
+    }
+
+    fn summarize_author(&self) -> String {
+        self.article.summarize_author()
+    }
+}
+
+#[test]
+fn cached_article_summarizes_once() {
+    struct CountingArticle {
+        calls: Cell<u32>,
+    }
+
+    impl Summary for CountingArticle {
+        fn summarize(&self) -> String {
+            self.calls.set(self.calls.get() + 1);
+            String::from("an expensive summary")
+        }
+
+        fn summarize_author(&self) -> String {
+            String::from("counter")
+        }
+    }
+
+    let cached = CachedArticle::new(CountingArticle { calls: Cell::new(0) });
+
+    assert_eq!(cached.summarize(), "an expensive summary");
+    assert_eq!(cached.summarize(), "an expensive summary");
+    assert_eq!(cached.summarize(), "an expensive summary");
+    assert_eq!(cached.article.calls.get(), 1);
+}
+
+#[test]
+fn set_content_clears_the_cache() {
+    let mut cached = CachedArticle::new(NewsArticle {
+        headline: String::from("Penguins win the Stanley Cup Championship!"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+        content: String::from("The Pittsburgh Penguins once again are the best hockey team in the NHL."),
+    });
+    cached.summarize();
+    assert!(cached.cache.borrow().is_some());
+
+    cached.set_content("Updated with quotes from the locker room.");
+
+    assert!(cached.cache.borrow().is_none());
+    assert_eq!(cached.article.content, "Updated with quotes from the locker room.");
+}
+
+// A feed that refreshes only needs to redisplay the items whose summaries changed. summary_changed compares what
+// summarize gives for two versions of the same item, and summary_diff hands back both summaries, before then after, when
+// they differ so the caller can show what changed:
+pub fn summary_changed<T: Summary>(before: &T, after: &T) -> bool {
+    summary_diff(before, after).is_some()
+}
+
+pub fn summary_diff<T: Summary>(before: &T, after: &T) -> Option<(String, String)> {
+    let before = before.summarize();
+    let after = after.summarize();
+
+    if before == after {
+        None
+    } else {
+        Some((before, after))
+    }
+}
+
+#[test]
+fn edited_tweet_has_a_changed_summary() {
+    let before = Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: false,
+    };
+    let after = Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, horses"),
+        reply: false,
+        retweet: false,
+    };
+
+    assert!(summary_changed(&before, &after));
+    assert_eq!(
+        summary_diff(&before, &after),
+        Some((
+            String::from("horse_ebooks: of course, as you probably already know, people"),
+            String::from("horse_ebooks: of course, as you probably already know, horses"),
+        ))
+    );
+}
+
+#[test]
+fn retweeted_tweet_has_the_same_summary() {
+    let before = Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: false,
+    };
+    let after = Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: true,
+    };
+
+    assert!(!summary_changed(&before, &after));
+    assert_eq!(summary_diff(&before, &after), None);
+}
+
+// Summarizing a big feed can be spread across threads (Chapter 16). A trait object can only be moved into another thread
+// if it is Send and shared with one if it is Sync, and Rust won't assume either for a plain dyn Summary, so the items have
+// to promise both in their type. concurrent_summaries puts each item in an Arc, gives a clone of it to its own thread to
+// summarize, and then joins the threads in the order the items came in, so the summaries line up with the items:
+use std::sync::Arc;
+use std::thread;
+
+pub fn concurrent_summaries(items: Vec<Box<dyn Summary + Send + Sync>>) -> Vec<String> {
+    let items: Vec<Arc<dyn Summary + Send + Sync>> = items.into_iter().map(Arc::from).collect();
+    let handles: Vec<thread::JoinHandle<String>> = items
+        .iter()
+        .map(|item| {
+            let item = Arc::clone(item);
+            thread::spawn(move || item.summarize())
+        })
+        .collect();
+
+    handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+}
+
+#[test]
+fn summarize_tweets_on_many_threads() {
+    let items: Vec<Box<dyn Summary + Send + Sync>> = vec![
+        Box::new(tweet("horse_ebooks", "of course, as you probably already know, people")),
+        Box::new(tweet("penguins", "We did it!")),
+        Box::new(tweet("iceburgh", "See you at the parade")),
+        Box::new(tweet("pittsburgh", "Roads closed downtown on Wednesday")),
+    ];
+
+    assert_eq!(
+        concurrent_summaries(items),
+        vec![
+            "horse_ebooks: of course, as you probably already know, people",
+            "penguins: We did it!",
+            "iceburgh: See you at the parade",
+            "pittsburgh: Roads closed downtown on Wednesday",
+        ]
+    );
+    assert!(concurrent_summaries(Vec::new()).is_empty());
+}
+
+// A feed could also put its shortest items first. sort_by_summary_len sorts a slice of summarizable items, such as a
+// mutably borrowed Vec, by how many characters their summaries have, shortest first. It counts chars rather than bytes,
+// so a summary with accents or emoji isn't treated as longer than it looks. sort_by_key is stable, so items with equally
+// long summaries keep their order:
+pub fn sort_by_summary_len<T: Summary>(items: &mut [T]) {
+    items.sort_by_key(|item| item.summarize().chars().count());
+}
+
+#[test]
+fn sort_tweets_by_summary_length() {
+    let mut tweets = vec![
+        tweet("horse_ebooks", "of course, as you probably already know, people"),
+        tweet("penguins", "We did it!"),
+        tweet("café", "Café ouvert!!"),
+        tweet("rustlang", "1.0 is out!"),
+        tweet("bob", "hi"),
+    ];
+
+    sort_by_summary_len(&mut tweets);
+
+    let order: Vec<&str> = tweets.iter().map(|tweet| tweet.username.as_str()).collect();
+    assert_eq!(order, vec!["bob", "café", "penguins", "rustlang", "horse_ebooks"]);
 }
 
-impl Summary for NewsArticle {
-    fn summerize(&self) -> String {
-        format!("{}, by {} ({})", self.headline, self.author, self.location)
+// To search a feed without summarizing every item again for each query, build_index summarizes each item once and records
+// which items every word appears in. Words are split on anything that isn't a letter or digit and lowercased, so "Cup!"
+// and "cup" are the same word. Each item's index is stored once per word, in order, no matter how many times the word
+// shows up in its summary. search then only needs to look the word up:
+pub fn build_index<T: Summary>(items: &[T]) -> HashMap<String, Vec<usize>> {
+    let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+
+    for (i, item) in items.iter().enumerate() {
+        for word in item.summarize().split(|c: char| !c.is_alphanumeric()) {
+            if word.is_empty() {
+                continue;
+            }
+
+            let positions = index.entry(word.to_lowercase()).or_default();
+            if positions.last() != Some(&i) {
+                positions.push(i);
+            }
+        }
     }
+
+    index
 }
 
-pub struct Tweet {
-    pub username: String,
-    pub content: String,
-    pub reply: bool,
-    pub retweet: bool,
+pub fn search(index: &HashMap<String, Vec<usize>>, word: &str) -> Vec<usize> {
+    index.get(&word.to_lowercase()).cloned().unwrap_or_default()
 }
 
-impl Summary for Tweet {
-    fn summerize(&self) -> String {
-        format!("{}: {}", self.username, self.content)
-    }
+#[test]
+fn search_an_index_of_tweets() {
+    let tweets = [
+        tweet("penguins", "We won the Cup! The cup is ours"),
+        tweet("rustlang", "1.0 is out!"),
+        tweet("iceburgh", "See the Cup at the parade"),
+    ];
+
+    let index = build_index(&tweets);
+
+    assert_eq!(search(&index, "cup"), vec![0, 2]);
+    assert_eq!(search(&index, "THE"), vec![0, 2]);
+    assert_eq!(search(&index, "is"), vec![0, 1]);
+    assert_eq!(search(&index, "rustlang"), vec![1]);
+    assert!(search(&index, "hockey").is_empty());
 }
-// Implementing a trait on a type is similar to implementing regular methods. The difference is that after impl, we put the trait name that we
-// want to implement, then use the for keyword, and then specify the name of the type we want to implement the trait for. Within the impl block,
-// we put the method signatures that the trait definition has defined. Instead of adding a semicolon after each signature, we use curly brackets
-// and fill in the method body with the specific behavior that we want the methods of the trait to have for the particular type.
 
-// Example showing how the above would be called (no different then a normal method):
-let tweet = Tweet {
-  username: String::from("horse_ebooks"),
-  content: String::from("of course, as you probably already know, people"),
-  reply: false,
-  retweet: false,
-};
+// Filtering a feed throws away the items that don't match. partition_by_summary keeps both groups instead: it takes the
+// items by value, checks each summary against the predicate, and returns the matching items first and the rest second,
+// each in their original order:
+pub fn partition_by_summary<T: Summary>(items: Vec<T>, pred: impl Fn(&str) -> bool) -> (Vec<T>, Vec<T>) {
+    items.into_iter().partition(|item| pred(&item.summarize()))
+}
 
-println!("1 new tweet: {}", tweet.summarize());
+#[test]
+fn partition_tweets_by_summary_length() {
+    let tweets = vec![
+        tweet("horse_ebooks", "of course, as you probably already know, people"),
+        tweet("penguins", "We did it!"),
+        tweet("pittsburgh", "Roads closed downtown on Wednesday"),
+        tweet("bob", "hi"),
+    ];
 
-// One restriction to note with trait implementations is that we can implement a trait on a type only if either the trait or the type is local to
-// our crate. For example, we can implement standard library traits like Display on a custom type like Tweet as part of our aggregator crate
-// functionality, because the type Tweet is local to our aggregator crate. We can also implement Summary on Vec<T> in our aggregator crate, because
-// the trait Summary is local to our aggregator crate.
+    let (long, short) = partition_by_summary(tweets, |summary| summary.len() > 20);
 
-// But we can’t implement external traits on external types. For example, we can’t implement the Display trait on Vec<T> within our aggregator crate,
-// because Display and Vec<T> are defined in the standard library and aren’t local to our aggregator crate. This restriction is part of a property of
-// programs called coherence, and more specifically the orphan rule, so named because the parent type is not present. This rule ensures that other
-// people’s code can’t break your code and vice versa. Without the rule, two crates could implement the same trait for the same type, and Rust wouldn’t
-// know which implementation to use.
+    let names = |tweets: &[Tweet]| -> Vec<String> { tweets.iter().map(|tweet| tweet.username.clone()).collect() };
+    assert_eq!(names(&long), vec!["horse_ebooks", "pittsburgh"]);
+    assert_eq!(names(&short), vec!["penguins", "bob"]);
+}
 
-// Default implementations
-// Sometimes it’s useful to have default behavior for some or all of the methods in a trait instead of requiring implementations for all methods on every
-// type. Then, as we implement the trait on a particular type, we can keep or override each method’s default behavior.
+// A feed shown in a fixed-width column can't let a long summary run past the edge. SummaryExt adds summarize_truncated to
+// every Summary type through a blanket impl, the same way MaximaExt adds running_max to iterators below, so none of the
+// impls have to change. A summary that fits comes back as it is; a longer one is cut so that, with "..." on the end to
+// show it was cut, it's exactly max_len chars. render_feed then puts each item's truncated summary on its own line,
+// numbered from 1:
+pub trait SummaryExt: Summary {
+    fn summarize_truncated(&self, max_len: usize) -> String {
+        let summary = self.summarize();
+        if summary.chars().count() <= max_len {
+            return summary;
+        }
+        if max_len < 3 {
+            return summary.chars().take(max_len).collect();
+        }
 
-// The below example shows how to specify a default string for the summarize method of the Summary trait instead of only defining the method signature,
-// as we did in before.
-pub trait Summary {
-    fn summerize(&self) -> String {
-        String::from("(Read more...)");
+        let mut truncated: String = summary.chars().take(max_len - 3).collect();
+        truncated.push_str("...");
+        truncated
     }
 }
-// To use a default implementation to summarize instances of NewsArticle instead of defining a custom implementation, we specify an empty impl block with
-impl Summary for NewsArticle {}
 
-// Even though we’re no longer defining the summarize method on NewsArticle directly, we’ve provided a default implementation and specified that NewsArticle
-// implements the Summary trait. As a result, we can still call the summarize method on an instance of NewsArticle, like this:
-let article = NewsArticle {
-    headline: String::from("Penguins win the Stanley Cup Championship!"),
-    location: String::from("Pittsburgh, PA, USA"),
-    author: String::from("Iceburgh"),
-    content: String::from("The Pittsburgh Penguins once again are the best hockey team in the NHL."),
-};
-println!("New article available! {}", article.summarize());
+impl<T: Summary + ?Sized> SummaryExt for T {}
 
-// Default implementations can call other methods in the same trait, even if those other methods don’t have a default implementation. In this way, a trait
-// can provide a lot of useful functionality and only require implementors to specify a small part of it. For example, we could define the Summary trait to
-// have a summarize_author method whose implementation is required, and then define a summarize method that has a default implementation that calls the
-// summarize_author method:
-pub trait Summary {
-    fn summerize_author(&self) -> String;
-    fn summerize(&self) -> String {
-        format!("(Read more from {}...)", self.summarize_author())
-    }
+pub fn render_feed<T: Summary>(items: &[T], max_len: usize) -> String {
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| format!("{}. {}", i + 1, item.summarize_truncated(max_len)))
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
-impl Summary for Tweet {
-    fn summerize_author(&self) -> String {
-        format!("@{}", self.username)
-    }
+#[test]
+fn render_a_feed_of_truncated_summaries() {
+    let tweets = [
+        tweet("horse_ebooks", "of course, as you probably already know, people"),
+        tweet("penguins", "We did it!"),
+        tweet("café", "Café ouvert aujourd'hui!"),
+    ];
+
+    assert_eq!(
+        render_feed(&tweets, 20),
+        "1. horse_ebooks: of ...\n2. penguins: We did it!\n3. café: Café ouvert..."
+    );
+    assert_eq!(tweets[1].summarize_truncated(2), "pe");
+    assert_eq!(render_feed::<Tweet>(&[], 20), "");
 }
 
-// After we define summarize_author, we can call summarize on instances of the Tweet struct, and the default implementation of summarize will call the
-// definition of summarize_author that we’ve provided. Because we’ve implemented summarize_author, the Summary trait has given us the behavior of the
-// summarize method without requiring us to write any more code.
+// Tweets can be compared with == now, but most Summary types can't, and what a reader of the feed notices is two items
+// that say the same thing anyway. dedup_feed keeps the first item with each summary and drops any later one that
+// summarizes the same way. HashSet::insert returns false for a summary it has already seen, so retain can use it as the
+// test for which items to keep:
+use std::collections::HashSet;
 
-// Traits as Parameters
-// Previously, we implemented the Summary trait on the NewsArticle and Tweet types. We can define a notify function that calls the summarize method on its
-// item parameter, which is of some type that implements the Summary trait. To do this, we can use the impl Trait syntax, like this:
-pub fn notify(item: impl Summary) {
-    println!("Breaking news! {}", item.summarize());
+pub fn dedup_feed<T: Summary>(items: Vec<T>) -> Vec<T> {
+    let mut seen = HashSet::new();
+    let mut items = items;
+
+    items.retain(|item| seen.insert(item.summarize()));
+    items
 }
-// Instead of a concrete type for the item parameter, we specify the impl keyword and the trait name. This parameter accepts any type that implements the
-// specified trait. In the body of notify, we can call any methods on item that come from the Summary trait, such as summarize. We can call notify and pass
-// in any instance of NewsArticle or Tweet. Code that calls the function with any other type, such as a String or an i32, won’t compile because those types
-// don’t implement Summary.
 
-// The impl Trait syntax works for straightforward cases but is actually syntax sugar for a longer form, which is called a trait bound; it looks like this:
-pub fn notify<T: Summary>(item: T) {
-    println!("Breaking news! {}", item.summarize());
+#[test]
+fn dedup_feed_keeps_the_first_of_each_summary() {
+    let feed = vec![
+        tweet("penguins", "We did it!"),
+        tweet("iceburgh", "See you at the parade"),
+        Tweet { retweet: true, ..tweet("penguins", "We did it!") },
+        tweet("iceburgh", "See you at the parade!"),
+    ];
+
+    let deduped = dedup_feed(feed);
+
+    let kept: Vec<(&str, bool)> = deduped.iter().map(|t| (t.username.as_str(), t.retweet)).collect();
+    assert_eq!(kept, vec![("penguins", false), ("iceburgh", false), ("iceburgh", false)]);
+    assert_eq!(deduped[2].content, "See you at the parade!");
 }
 
-// The impl Trait syntax is convenient and makes for more concise code in simple cases. The trait bound syntax can express more complexity in other cases. For
-// example, we can have two parameters that implement Summary. Using the impl Trait syntax looks like this:
-pub fn notify(item1: impl Summary, item2: impl Summary) {}
+// summarize returns a String, so every call allocates, even for a type whose summary is text it already holds. SummaryCow
+// returns a Cow<str> instead, a clone-on-write value that's either a borrowed &str or an owned String. StaticTweet's text
+// lives for the whole program, so its summary borrows it and never allocates. Tweet builds its summary with format!, so it
+// hands back the owned String. Callers treat both the same, since a Cow<str> derefs to &str either way:
+use std::borrow::Cow;
 
-// If we wanted this function to allow item1 and item2 to have different types, using impl Trait would be appropriate (as long as both types implement Summary).
-// If we wanted to force both parameters to have the same type, that’s only possible to express using a trait bound, like this:
-pub fn notify<T: Summary>(item1: T, item2: T) {}
+pub trait SummaryCow {
+    fn summarize_cow(&self) -> Cow<'_, str>;
+}
 
-// We can also specify more than one trait bound. Say we wanted notify to use display formatting on item as well as the summarize method: we specify in the notify
-// definition that item must implement both Display and Summary. We can do so using the + syntax:
-pub fn notify(item: impl Summary + Display) {}
+pub struct StaticTweet {
+    pub text: &'static str,
+}
 
-// Or
-pub fn notify<T: Summary + Display>(item: T) {}
+impl SummaryCow for StaticTweet {
+    fn summarize_cow(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.text)
+    }
+}
 
-// Using too many trait bounds has its downsides. Each generic has its own trait bounds, so functions with multiple generic type parameters can contain lots of trait
-// bound information between the function’s name and its parameter list, making the function signature hard to read. For this reason, Rust has alternate syntax for
-// specifying trait bounds inside a where clause after the function signature. So instead of writing this:
-fn some_function<T: Display + Clone, U: Clone + Debug>(t: T, u: U) -> i32 {}
+impl SummaryCow for Tweet {
+    fn summarize_cow(&self) -> Cow<'_, str> {
+        Cow::Owned(self.summarize())
+    }
+}
 
-// We can do this:
-fn some_function<T, U>(t: T, u: U) -> i32
-where
-    T: Display + Clone,
-    U: Clone + Debug,
-{
-    println!("function body");
+#[test]
+fn static_tweet_summary_is_borrowed() {
+    let tweet = StaticTweet { text: "We did it!" };
+    let summary = tweet.summarize_cow();
+
+    assert!(matches!(summary, Cow::Borrowed(_)));
+    assert_eq!(summary, "We did it!");
+    assert!(std::ptr::eq(summary.as_ptr(), tweet.text.as_ptr()));
 }
 
-// Returning Types that Implement Traits.
-// We can also use the impl Trait syntax in the return position to return a value of some type that implements a trait, as shown here:
-fn returns_summerizable() -> impl Summary {
-    Tweet {
-        username: String::from("horse_ebooks"),
-        content: String::from("of course, as you probably already know, people"),
+#[test]
+fn tweet_summary_is_owned() {
+    let tweet = Tweet {
+        username: String::from("penguins"),
+        content: String::from("We did it!"),
         reply: false,
         retweet: false,
-    }
+    };
+    let summary = tweet.summarize_cow();
+
+    assert!(matches!(summary, Cow::Owned(_)));
+    assert_eq!(summary, tweet.summarize());
 }
-// By using impl Summary for the return type, we specify that the returns_summarizable function returns some type that implements the Summary trait without naming
-// the concrete type. In this case, returns_summarizable returns a Tweet, but the code calling this function doesn’t know that. The ability to return a type that
-// is only specified by the trait it implements is especially useful in the context of closures and iterators, which we cover in Chapter 13. Closures and iterators
-// create types that only the compiler knows or types that are very long to specify. The impl Trait syntax lets you concisely specify that a function returns some
-// type that implements the Iterator trait without needing to write out a very long type.
 
-// However, you can only use impl Trait if you’re returning a single type. For example, this code that returns either a NewsArticle or a Tweet with the return type
-// specified as impl Summary wouldn’t work. Does not compile:
-fn returns_summarizable(switch: bool) -> impl Summary {
-    if switch {
-        NewsArticle {
-            headline: String::from("Penguins win the Stanley Cup Championship!"),
-            location: String::from("Pittsburgh, PA, USA"),
-            author: String::from("Iceburgh"),
-            content: String::from("The Pittsburgh Penguins once again are the best
-            hockey team in the NHL."),
+// For analytics over a feed, like the most used hashtag or the user who posts the most, most_common counts how many times
+// each item shows up and returns the most frequent one with its count. The counts go in a HashMap, which needs Eq and
+// Hash, and the item is cloned as the key the first time it's seen. A HashMap doesn't keep any order, so the cloned
+// items also go in a Vec in the order they were first seen; walking that and only replacing the best on a strictly
+// higher count means the first-seen item wins a tie. An empty iterator has no most common item:
+pub fn most_common<T, I>(iter: I) -> Option<(T, usize)>
+where
+    T: Eq + std::hash::Hash + Clone,
+    I: IntoIterator<Item = T>,
+{
+    let mut counts: HashMap<T, usize> = HashMap::new();
+    let mut first_seen = Vec::new();
+
+    for item in iter {
+        let count = counts.entry(item.clone()).or_insert(0);
+        if *count == 0 {
+            first_seen.push(item);
         }
-    } else {
-        Tweet {
-            username: String::from("horse_ebooks"),
-            content: String::from("of course, as you probably already know, people"),
-            reply: false,
-            retweet: false,
+        *count += 1;
+    }
+
+    let mut best: Option<(T, usize)> = None;
+    for item in first_seen {
+        let count = counts[&item];
+        let is_better = match &best {
+            Some((_, best_count)) => count > *best_count,
+            None => true,
+        };
+        if is_better {
+            best = Some((item, count));
         }
     }
+
+    best
+}
+
+#[test]
+fn most_common_hashtag() {
+    let tags = vec!["rust", "pens", "rust", "cup", "rust", "pens"];
+
+    assert_eq!(most_common(tags), Some(("rust", 3)));
+    assert_eq!(most_common(Vec::<String>::new()), None);
+}
+
+#[test]
+fn most_common_tie_goes_to_the_first_seen() {
+    let users = vec![
+        String::from("iceburgh"),
+        String::from("penguins"),
+        String::from("penguins"),
+        String::from("iceburgh"),
+        String::from("rustlang"),
+    ];
+
+    assert_eq!(most_common(users), Some((String::from("iceburgh"), 2)));
 }
 
 // With our non-generic versions of the largest function, we were only trying to find the largest i32 or char. As discussed in the “Stack-Only Data: Copy” section
@@ -477,6 +2846,239 @@ fn main() {
 // Another way we could implement largest is for the function to return a reference to a T value in the slice. If we change the return type to &T instead of T,
 // thereby changing the body of the function to return a reference, we wouldn’t need the Clone or Copy trait bounds and we could avoid heap allocations.
 
+// largest walks the whole slice to hand back one value. With the same PartialOrd + Copy bounds we can instead wrap any iterator in an adaptor that
+// lazily yields each new running maximum as it's found, so [3, 1, 4, 1, 5] produces 3, 4, 5. An extension trait with a blanket impl gives every
+// such iterator a running_max method, the same way the standard library's adaptors hang off Iterator:
+struct Maxima<I: Iterator> {
+    iter: I,
+    current: Option<I::Item>,
+}
+
+impl<I> Iterator for Maxima<I>
+where
+    I: Iterator,
+    I::Item: PartialOrd + Copy,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in self.iter.by_ref() {
+            let is_new_max = match self.current {
+                Some(current) => item > current,
+                None => true,
+            };
+
+            if is_new_max {
+                self.current = Some(item);
+                return Some(item);
+            }
+        }
+
+        None
+    }
+}
+
+trait MaximaExt: Iterator + Sized {
+    fn running_max(self) -> Maxima<Self> {
+        Maxima {
+            iter: self,
+            current: None,
+        }
+    }
+}
+
+impl<I: Iterator> MaximaExt for I {}
+
+#[test]
+fn running_max_yields_each_new_maximum() {
+    let maxima: Vec<i32> = vec![3, 1, 4, 1, 5].into_iter().running_max().collect();
+
+    assert_eq!(maxima, vec![3, 4, 5]);
+}
+
+// A sliding window asks a harder question: the largest of each run of k values in a row, so [1, 3, 2, 5, 4] with k = 2
+// produces 3, 3, 5, 5. Calling largest on every window would compare each value up to k times. WindowMax instead keeps a
+// VecDeque of the values that could still be the maximum of some window, largest at the front, each with its position:
+// a new value first pushes out every smaller value at the back, since none of them can win a window that includes it,
+// and the front value is dropped once it slides out of the window. Each value goes in and out of the deque at most once.
+// A window size of 0 and an iterator shorter than k both produce nothing, the same as slice::windows:
+use std::collections::VecDeque;
+
+struct WindowMax<I: Iterator> {
+    iter: I,
+    k: usize,
+    position: usize,
+    candidates: VecDeque<(usize, I::Item)>,
+}
+
+impl<I> Iterator for WindowMax<I>
+where
+    I: Iterator,
+    I::Item: PartialOrd + Copy,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.k == 0 {
+            return None;
+        }
+
+        for item in self.iter.by_ref() {
+            while self.candidates.back().is_some_and(|&(_, back)| back <= item) {
+                self.candidates.pop_back();
+            }
+            self.candidates.push_back((self.position, item));
+
+            let (position, k) = (self.position, self.k);
+            if self.candidates.front().is_some_and(|&(start, _)| start + k <= position) {
+                self.candidates.pop_front();
+            }
+
+            self.position += 1;
+            if self.position >= k {
+                return self.candidates.front().map(|&(_, max)| max);
+            }
+        }
+
+        None
+    }
+}
+
+trait WindowMaxExt: Iterator + Sized {
+    fn window_max(self, k: usize) -> WindowMax<Self> {
+        WindowMax {
+            iter: self,
+            k,
+            position: 0,
+            candidates: VecDeque::new(),
+        }
+    }
+}
+
+impl<I: Iterator> WindowMaxExt for I {}
+
+#[test]
+fn window_max_of_each_pair() {
+    let maxima: Vec<i32> = vec![1, 3, 2, 5, 4].into_iter().window_max(2).collect();
+
+    assert_eq!(maxima, vec![3, 3, 5, 5]);
+}
+
+#[test]
+fn window_max_edge_sizes() {
+    let values = [1, 3, 2, 5, 4];
+
+    assert_eq!(values.iter().copied().window_max(1).collect::<Vec<i32>>(), vec![1, 3, 2, 5, 4]);
+    assert_eq!(values.iter().copied().window_max(3).collect::<Vec<i32>>(), vec![3, 5, 5]);
+    assert_eq!(values.iter().copied().window_max(5).collect::<Vec<i32>>(), vec![5]);
+    assert!(values.iter().copied().window_max(6).next().is_none());
+    assert!(values.iter().copied().window_max(0).next().is_none());
+}
+
+// largest also panics when it's handed an empty slice, because list[0] is out of bounds. Following Chapter 9, we can
+// give callers a recoverable path instead: checked_largest returns a Result whose error is a small EmptyInput type.
+// Implementing Display and std::error::Error for it means it behaves like any other error, including with ?.
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+struct EmptyInput;
+
+impl fmt::Display for EmptyInput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot find the largest value of an empty list")
+    }
+}
+
+impl Error for EmptyInput {}
+
+fn checked_largest<T: PartialOrd + Copy>(list: &[T]) -> Result<T, EmptyInput> {
+    let (&first, rest) = list.split_first().ok_or(EmptyInput)?;
+    let mut largest = first;
+
+    for &item in rest {
+        if item > largest {
+            largest = item;
+        }
+    }
+
+    Ok(largest)
+}
+
+#[test]
+fn checked_largest_of_a_populated_slice() {
+    assert_eq!(checked_largest(&[34, 50, 25, 100, 65]), Ok(100));
+    assert_eq!(checked_largest(&['y', 'm', 'a', 'q']), Ok('y'));
+}
+
+#[test]
+fn checked_largest_of_an_empty_slice() {
+    let empty: [i32; 0] = [];
+    let err = checked_largest(&empty).unwrap_err();
+
+    assert_eq!(err, EmptyInput);
+    assert_eq!(err.to_string(), "cannot find the largest value of an empty list");
+}
+
+// Comparing doesn't have to mean copying values out, either. swap_if_greater only needs PartialOrd: it compares the two
+// values through their references and, if they're out of order, std::mem::swap exchanges them in place, so it works for
+// types like String that aren't Copy. It returns whether it had to swap:
+fn swap_if_greater<T: PartialOrd>(a: &mut T, b: &mut T) -> bool {
+    if *a > *b {
+        std::mem::swap(a, b);
+        true
+    } else {
+        false
+    }
+}
+
+#[test]
+fn swap_strings_that_are_out_of_order() {
+    let mut a = String::from("pear");
+    let mut b = String::from("apple");
+
+    assert!(swap_if_greater(&mut a, &mut b));
+    assert_eq!((a.as_str(), b.as_str()), ("apple", "pear"));
+}
+
+#[test]
+fn leave_strings_that_are_in_order() {
+    let mut a = String::from("apple");
+    let mut b = String::from("pear");
+
+    assert!(!swap_if_greater(&mut a, &mut b));
+    assert_eq!((a.as_str(), b.as_str()), ("apple", "pear"));
+}
+
+// The loop in largest is what the iterator method reduce does for us: it starts from the first item and folds the rest
+// into it with a closure, and because it has nothing to start from on an empty iterator, it returns an Option instead of
+// panicking on list[0]. largest_fold keeps the same bounds and, like largest, only replaces the current value with a
+// strictly greater one:
+fn largest_fold<T: PartialOrd + Copy>(list: &[T]) -> Option<T> {
+    list.iter()
+        .copied()
+        .reduce(|largest, item| if item > largest { item } else { largest })
+}
+
+#[test]
+fn largest_fold_matches_largest() {
+    let number_list = vec![34, 50, 25, 100, 65];
+    let char_list = vec!['y', 'm', 'a', 'q'];
+    let float_list = vec![1.5, -2.0, 0.25];
+
+    assert_eq!(largest_fold(&number_list), Some(largest(&number_list)));
+    assert_eq!(largest_fold(&char_list), Some(largest(&char_list)));
+    assert_eq!(largest_fold(&float_list), Some(largest(&float_list)));
+    assert_eq!(largest_fold(&[7]), Some(7));
+}
+
+#[test]
+fn largest_fold_of_an_empty_slice() {
+    let empty: [i32; 0] = [];
+
+    assert_eq!(largest_fold(&empty), None);
+}
+
 // Using Trait Bounds to Conditionally Implement Methods
 // By using a trait bound with an impl block that uses generic type parameters, we can implement methods conditionally for types that implement the specified traits.
 // For example, the type Pair<T> in Listing 10-16 always implements the new function. But Pair<T> only implements the cmp_display method if its inner type T implements
@@ -515,6 +3117,25 @@ impl<T: Display> ToString for T {
 // trait. For example, we can turn integers into their corresponding String values like this because integers implement Display.
 let s = 3.to_string();
 
+// We can lean on that same blanket impl from our own generic code. print_framed accepts anything that implements Display, turns it into a
+// String with to_string, and frames it in # characters sized to the displayed width. The ?Sized bound lets us pass a plain &str too:
+fn print_framed<T: Display + ?Sized>(value: &T) -> String {
+    let output = value.to_string();
+    let border = "#".repeat(output.chars().count() + 4);
+
+    format!("{}\n# {} #\n{}", border, output, border)
+}
+
+#[test]
+fn frames_an_integer() {
+    assert_eq!(print_framed(&42), "######\n# 42 #\n######");
+}
+
+#[test]
+fn frames_a_str() {
+    assert_eq!(print_framed("hi"), "######\n# hi #\n######");
+}
+
 // Traits and trait bounds let us write code that uses generic type parameters to reduce duplication but also specify to the compiler that we want the generic type to
 // have particular behavior. The compiler can then use the trait bound information to check that all the concrete types used with our code provide the correct behavior.
 // In dynamically typed languages, we would get an error at runtime if we called a method on a type that the type didn’t implement. But Rust moves these errors to compile
@@ -739,6 +3360,57 @@ fn first_word<'a>(s: &'a str) -> &'a str {}
 // Now all the references in this function signature have lifetimes, and the compiler can continue its analysis without needing the programmer to annotate the lifetimes
 // in this function signature.
 
+// The same two rules cover nth_word, which returns the nth word of s counting from 0. The usize parameter isn't a reference,
+// so s is still the only input lifetime and the returned slice borrows from it, just like first_word's does. Instead of
+// looking for spaces byte by byte, split_whitespace skips over any run of spaces, tabs, or newlines, including ones at the
+// start and end, so only real words are counted. There's nothing to return when s has n words or fewer, so the slice comes
+// back in an Option:
+fn nth_word(s: &str, n: usize) -> Option<&str> {
+    s.split_whitespace().nth(n)
+}
+
+#[test]
+fn nth_word_of_a_sentence() {
+    let s = String::from("  hello   wide\tworld  ");
+
+    assert_eq!(nth_word(&s, 0), Some("hello"));
+    assert_eq!(nth_word(&s, 1), Some("wide"));
+    assert_eq!(nth_word(&s, 2), Some("world"));
+}
+
+#[test]
+fn nth_word_past_the_last_word() {
+    assert_eq!(nth_word("hello world", 2), None);
+    assert_eq!(nth_word("   ", 0), None);
+    assert_eq!(nth_word("", 0), None);
+}
+
+// last_word is first_word from the other end. Its signature is elided the same way, so the word it returns is a slice of
+// s too. A string with only one word gives back that word without the whitespace around it, and since an empty or
+// all-whitespace string has no last word, it returns an empty slice rather than an Option, the way first_word always
+// returns some slice:
+fn last_word(s: &str) -> &str {
+    s.split_whitespace().last().unwrap_or("")
+}
+
+#[test]
+fn last_word_of_several_words() {
+    assert_eq!(last_word("hello wide world"), "world");
+    assert_eq!(last_word("hello world \n"), "world");
+}
+
+#[test]
+fn last_word_of_a_single_word() {
+    assert_eq!(last_word("hello"), "hello");
+    assert_eq!(last_word("  hello  "), "hello");
+}
+
+#[test]
+fn last_word_of_an_empty_string() {
+    assert_eq!(last_word(""), "");
+    assert_eq!(last_word(" \t "), "");
+}
+
 // Let’s look at another example, this time using the longest function that had no lifetime parameters when we started working with it before:
 fn longest(x: &str, y: &str) -> &str {}
 
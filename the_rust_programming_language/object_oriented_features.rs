@@ -74,6 +74,202 @@ impl AveragedCollection {
 // The average method returns the value in the average field, allowing external code to read the average but
 // not modify it.
 
+// Because the fields stay private, the only other way in is through traits we implement ourselves. Implementing
+// FromIterator lets callers collect straight into an AveragedCollection, and since we own the internals we can
+// push every item first and compute the average once at the end instead of after each element:
+use std::iter::FromIterator;
+
+impl FromIterator<i32> for AveragedCollection {
+    fn from_iter<I: IntoIterator<Item = i32>>(iter: I) -> Self {
+        let mut collection = AveragedCollection {
+            list: iter.into_iter().collect(),
+            average: 0.0,
+        };
+        collection.update_average();
+        collection
+    }
+}
+
+#[test]
+fn collect_into_averaged_collection() {
+    let collection: AveragedCollection = (1..=10).collect();
+
+    assert_eq!(collection.average(), 5.5);
+}
+
+// The cached average also gives us the spread of the values for free. variance is the population variance, the mean of
+// each value's squared distance from the average, and std_dev is its square root. An empty collection has no spread to
+// report, so both return None rather than NaN:
+impl AveragedCollection {
+    pub fn variance(&self) -> Option<f64> {
+        if self.list.is_empty() {
+            return None;
+        }
+
+        let total: f64 = self
+            .list
+            .iter()
+            .map(|&value| (f64::from(value) - self.average).powi(2))
+            .sum();
+        Some(total / self.list.len() as f64)
+    }
+
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+}
+
+#[test]
+fn variance_and_std_dev_of_a_collection() {
+    let collection: AveragedCollection = vec![2, 4, 4, 4, 5, 5, 7, 9].into_iter().collect();
+
+    assert_eq!(collection.average(), 5.0);
+    assert_eq!(collection.variance(), Some(4.0));
+    assert_eq!(collection.std_dev(), Some(2.0));
+}
+
+#[test]
+fn variance_of_an_empty_collection() {
+    let collection: AveragedCollection = Vec::new().into_iter().collect();
+
+    assert_eq!(collection.variance(), None);
+    assert_eq!(collection.std_dev(), None);
+}
+
+// remove can only take the last value off the end. remove_at takes the value at any index out instead, using Vec::remove
+// to shift the values after it down. Vec::remove panics on an index past the end, so remove_at checks the index first and
+// returns None for one that's out of range, leaving the collection and its average untouched. Because it goes through
+// update_average like add and remove do, the cached average still can't fall out of sync:
+impl AveragedCollection {
+    pub fn remove_at(&mut self, index: usize) -> Option<i32> {
+        if index >= self.list.len() {
+            return None;
+        }
+
+        let value = self.list.remove(index);
+        self.update_average();
+        Some(value)
+    }
+}
+
+#[test]
+fn remove_a_middle_value() {
+    let mut collection: AveragedCollection = vec![1, 10, 3].into_iter().collect();
+
+    assert_eq!(collection.remove_at(1), Some(10));
+    assert_eq!(collection.average(), 2.0);
+    assert_eq!(collection.remove(), Some(3));
+    assert_eq!(collection.remove(), Some(1));
+}
+
+#[test]
+fn remove_at_an_out_of_range_index() {
+    let mut collection: AveragedCollection = vec![1, 2, 3].into_iter().collect();
+
+    assert_eq!(collection.remove_at(3), None);
+    assert_eq!(collection.average(), 2.0);
+}
+
+// To save a collection and load it again, to_csv writes the values out separated by commas. The average isn't written
+// since it can always be worked out from the values: from_csv parses every value first and then collects them, so the
+// average is computed once through FromIterator. Spaces around a value are ignored, and an empty string is an empty
+// collection. If a value isn't an i32, from_csv returns an Err naming that value and where it was:
+impl AveragedCollection {
+    pub fn to_csv(&self) -> String {
+        self.list
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    pub fn from_csv(s: &str) -> Result<AveragedCollection, String> {
+        if s.trim().is_empty() {
+            return Ok(Vec::new().into_iter().collect());
+        }
+
+        let mut values = Vec::new();
+        for (i, token) in s.split(',').enumerate() {
+            match token.trim().parse::<i32>() {
+                Ok(value) => values.push(value),
+                Err(_) => return Err(format!("value {} ({:?}) is not an i32", i + 1, token.trim())),
+            }
+        }
+
+        Ok(values.into_iter().collect())
+    }
+}
+
+#[test]
+fn csv_round_trip() {
+    let collection: AveragedCollection = vec![3, -1, 10].into_iter().collect();
+    let csv = collection.to_csv();
+    assert_eq!(csv, "3,-1,10");
+
+    let loaded = AveragedCollection::from_csv(&csv).unwrap();
+    assert_eq!(loaded.to_csv(), "3,-1,10");
+    assert_eq!(loaded.average(), 4.0);
+
+    assert_eq!(AveragedCollection::from_csv(" 1, 2 ,3").unwrap().average(), 2.0);
+    assert_eq!(AveragedCollection::from_csv("").unwrap().to_csv(), "");
+}
+
+#[test]
+fn from_csv_reports_the_bad_value() {
+    assert_eq!(
+        AveragedCollection::from_csv("1,2,three,4").err(),
+        Some(String::from("value 3 (\"three\") is not an i32"))
+    );
+    assert_eq!(
+        AveragedCollection::from_csv("1,,2").err(),
+        Some(String::from("value 2 (\"\") is not an i32"))
+    );
+}
+
+// Not every caller needs the cached average, though. Pulling the calculation out into an Average trait lets any
+// slice of numbers report its mean on demand. An empty slice has no average, so rather than dividing by zero and
+// getting NaN, average returns None:
+pub trait Average {
+    fn average(&self) -> Option<f64>;
+}
+
+impl Average for [i32] {
+    fn average(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let total: i64 = self.iter().map(|&value| i64::from(value)).sum();
+        Some(total as f64 / self.len() as f64)
+    }
+}
+
+impl Average for [f64] {
+    fn average(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let total: f64 = self.iter().sum();
+        Some(total / self.len() as f64)
+    }
+}
+
+#[test]
+fn average_of_slices() {
+    assert_eq!([1, 2, 3, 4][..].average(), Some(2.5));
+    assert_eq!([1.5, 2.5, 3.5][..].average(), Some(2.5));
+}
+
+#[test]
+fn average_of_an_empty_slice() {
+    let ints: &[i32] = &[];
+    let floats: &[f64] = &[];
+
+    assert_eq!(ints.average(), None);
+    assert_eq!(floats.average(), None);
+}
+
 // Because we’ve encapsulated the implementation details of the struct AveragedCollection, we can easily
 // change aspects, such as the data structure, in the future. For instance, we could use a HashSet<i32>
 // instead of a Vec<i32> for the list field. As long as the signatures of the add, remove, and average public
@@ -152,8 +348,45 @@ impl AveragedCollection {
 // languages: their specific purpose is to allow abstraction across common behavior.
 
 // Below shows how to define a trait named Draw with one method named draw:
-pub trait Draw {
+pub trait Draw: AsAny {
     fn draw(&self);
+
+    // Not every widget reacts to clicks, so by default a component isn't Clickable
+    fn as_clickable(&self) -> Option<&dyn Clickable> {
+        None
+    }
+
+    // The (width, height) a component takes up on screen; widgets without a size report (0, 0)
+    fn bounds(&self) -> (u32, u32) {
+        (0, 0)
+    }
+
+    // A short text form of what draw would put on screen, for debugging and tests
+    fn describe(&self) -> String {
+        String::from("component")
+    }
+
+    // Where the component sits in the stack of widgets: lower values are drawn first, so higher ones end up on top
+    fn z_order(&self) -> i32 {
+        0
+    }
+
+    // The lines of text draw would put on screen, for widgets that know how to render themselves as ASCII art
+    fn render_ascii(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+// Every widget can hand itself back as a &dyn Any, which lets code holding a &dyn Draw ask for the concrete type back with
+// downcast_ref. The blanket impl covers every sized 'static type, so implementing Draw still only takes draw:
+pub trait AsAny {
+    fn as_any(&self) -> &dyn std::any::Any;
+}
+
+impl<T: std::any::Any> AsAny for T {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
 }
 
 // This syntax should look familiar from our discussions on how to define traits in Chapter 10. Next comes
@@ -182,12 +415,54 @@ pub struct Button {
     pub width: u32,
     pub height: u32,
     pub label: String,
+    pub z: i32,
 }
 
 impl Draw for Button {
     fn draw(&self) {
         // code to draw a button
     }
+
+    fn as_clickable(&self) -> Option<&dyn Clickable> {
+        Some(self)
+    }
+
+    fn bounds(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn describe(&self) -> String {
+        format!("Button({})", self.label)
+    }
+
+    fn z_order(&self) -> i32 {
+        self.z
+    }
+
+    fn render_ascii(&self) -> Vec<String> {
+        let width = self.width as usize;
+        let height = self.height as usize;
+        if width < 2 || height < 2 {
+            return Vec::new();
+        }
+
+        let inner_width = width - 2;
+        let label_row = (height - 1) / 2;
+        let label = self.display_label();
+        let border = format!("+{}+", "-".repeat(inner_width));
+
+        (0..height)
+            .map(|row| {
+                if row == 0 || row == height - 1 {
+                    border.clone()
+                } else if row == label_row {
+                    format!("|{:^width$}|", label, width = inner_width)
+                } else {
+                    format!("|{}|", " ".repeat(inner_width))
+                }
+            })
+            .collect()
+    }
 }
 
 // If someone using our library decides to implement a SelectBox struct that has width, height, and options
@@ -198,12 +473,21 @@ struct SelectBox {
     width: u32,
     height: u32,
     options: Vec<String>,
+    selected: Option<usize>,
 }
 
 impl Draw for SelectBox {
     fn draw(&self) {
         // some code to draw a select box
     }
+
+    fn bounds(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn describe(&self) -> String {
+        format!("SelectBox({})", self.options.join(", "))
+    }
 }
 
 // Our library’s user can now write their main function to create a Screen instance. To the Screen instance,
@@ -223,11 +507,13 @@ fn main() {
                     String::from("Maybe"),
                     String::from("No")
                 ],
+                selected: None,
             }),
             Box::new(Button {
                 width: 50,
                 height: 10,
                 label: String::from("OK"),
+                z: 0,
             }),
         ],
     };
@@ -251,6 +537,810 @@ fn main() {
 // value doesn’t implement a method but we call it anyway. Rust won’t compile our code if the values don’t implement
 // the traits that the trait objects need.
 
+// Some widgets also respond to clicks. Rather than keeping clickable widgets in a second Vec that has to stay in sync
+// with components, Draw has an as_clickable method that returns None by default. Widgets that implement Clickable
+// override it to return Some(self), so Screen can ask any trait object whether it can be clicked. Because on_click
+// only gets &self, a Checkbox keeps its checked state in a Cell so it can toggle itself:
+use std::cell::Cell;
+
+pub trait Clickable {
+    fn on_click(&self) -> String;
+}
+
+impl Clickable for Button {
+    fn on_click(&self) -> String {
+        self.label.clone()
+    }
+}
+
+pub struct Checkbox {
+    pub label: String,
+    pub checked: Cell<bool>,
+}
+
+impl Draw for Checkbox {
+    fn draw(&self) {
+        // code to draw a checkbox
+    }
+
+    fn as_clickable(&self) -> Option<&dyn Clickable> {
+        Some(self)
+    }
+
+    fn describe(&self) -> String {
+        format!("Checkbox({})", self.label)
+    }
+}
+
+impl Clickable for Checkbox {
+    fn on_click(&self) -> String {
+        self.checked.set(!self.checked.get());
+
+        if self.checked.get() {
+            format!("{} checked", self.label)
+        } else {
+            format!("{} unchecked", self.label)
+        }
+    }
+}
+
+impl Screen {
+    pub fn click_at(&self, index: usize) -> Option<String> {
+        self.components
+            .get(index)?
+            .as_clickable()
+            .map(|clickable| clickable.on_click())
+    }
+}
+
+#[test]
+fn clicking_components_on_a_screen() {
+    let screen = Screen {
+        components: vec![
+            Box::new(Button {
+                width: 50,
+                height: 10,
+                label: String::from("OK"),
+                z: 0,
+            }),
+            Box::new(SelectBox {
+                width: 75,
+                height: 10,
+                options: vec![String::from("Yes"), String::from("No")],
+                selected: None,
+            }),
+            Box::new(Checkbox {
+                label: String::from("Remember me"),
+                checked: Cell::new(false),
+            }),
+        ],
+    };
+
+    assert_eq!(screen.click_at(0), Some(String::from("OK")));
+    assert_eq!(screen.click_at(1), None);
+    assert_eq!(screen.click_at(2), Some(String::from("Remember me checked")));
+    assert_eq!(screen.click_at(2), Some(String::from("Remember me unchecked")));
+    assert_eq!(screen.click_at(3), None);
+}
+
+// Spelling out width, height, and label for every Button gets repetitive when building a Screen. Button::new takes just
+// the label (anything that converts into a String) and picks the same 50x10 size used above, and with_size consumes and
+// returns the button so the size can be chained on when it matters:
+impl Button {
+    pub fn new(label: impl Into<String>) -> Button {
+        Button {
+            width: 50,
+            height: 10,
+            label: label.into(),
+            z: 0,
+        }
+    }
+
+    pub fn with_size(mut self, width: u32, height: u32) -> Button {
+        self.width = width;
+        self.height = height;
+        self
+    }
+}
+
+#[test]
+fn build_a_button_fluently() {
+    let button = Button::new("Cancel").with_size(80, 20);
+
+    assert_eq!(button.label, "Cancel");
+    assert_eq!(button.width, 80);
+    assert_eq!(button.height, 20);
+    assert_eq!(Button::new(String::from("OK")).width, 50);
+}
+
+// A SelectBox isn't much use if it can't remember which option was picked, so it now has a selected index. select
+// checks the index against options before storing it, and selected_value hands back the chosen option, if any:
+impl SelectBox {
+    fn select(&mut self, index: usize) -> Result<(), String> {
+        if index >= self.options.len() {
+            return Err(format!(
+                "option {} is out of range for {} options",
+                index,
+                self.options.len()
+            ));
+        }
+
+        self.selected = Some(index);
+        Ok(())
+    }
+
+    fn selected_value(&self) -> Option<&str> {
+        self.selected.map(|index| self.options[index].as_str())
+    }
+}
+
+#[test]
+fn select_an_option() {
+    let mut select_box = SelectBox {
+        width: 75,
+        height: 10,
+        options: vec![String::from("Yes"), String::from("Maybe"), String::from("No")],
+        selected: None,
+    };
+    assert_eq!(select_box.selected_value(), None);
+
+    select_box.select(1).unwrap();
+    assert_eq!(select_box.selected_value(), Some("Maybe"));
+
+    assert_eq!(
+        select_box.select(3),
+        Err(String::from("option 3 is out of range for 3 options"))
+    );
+    assert_eq!(select_box.selected_value(), Some("Maybe"));
+}
+
+// Because bounds is part of Draw, Screen can search its components without knowing their concrete types. find takes a
+// predicate over &dyn Draw and returns the first component it accepts, still as a trait object:
+impl Screen {
+    pub fn find(&self, pred: impl Fn(&dyn Draw) -> bool) -> Option<&dyn Draw> {
+        self.components
+            .iter()
+            .map(|component| component.as_ref())
+            .find(|&component| pred(component))
+    }
+}
+
+#[test]
+fn find_a_component_by_its_bounds() {
+    let screen = Screen {
+        components: vec![
+            Box::new(Button::new("OK")),
+            Box::new(SelectBox {
+                width: 75,
+                height: 30,
+                options: vec![String::from("Yes"), String::from("No")],
+                selected: None,
+            }),
+            Box::new(Button::new("Cancel").with_size(80, 40)),
+        ],
+    };
+
+    let tall = screen.find(|component| component.bounds().1 > 10).unwrap();
+    assert_eq!(tall.bounds(), (75, 30));
+    assert!(tall.as_clickable().is_none());
+
+    assert!(screen.find(|component| component.bounds().0 > 100).is_none());
+}
+
+// When something looks wrong on screen it helps to know which component drew what. run_indexed draws every component
+// like run does, but walks them with iter().enumerate() so it can pair each position with that component's describe
+// output:
+impl Screen {
+    pub fn run_indexed(&self) -> Vec<(usize, String)> {
+        self.components
+            .iter()
+            .enumerate()
+            .map(|(index, component)| {
+                component.draw();
+                (index, component.describe())
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn run_a_screen_with_indices() {
+    let screen = Screen {
+        components: vec![
+            Box::new(SelectBox {
+                width: 75,
+                height: 10,
+                options: vec![String::from("Yes"), String::from("No")],
+                selected: None,
+            }),
+            Box::new(Button::new("OK")),
+            Box::new(Checkbox {
+                label: String::from("Remember me"),
+                checked: Cell::new(false),
+            }),
+        ],
+    };
+
+    assert_eq!(
+        screen.run_indexed(),
+        vec![
+            (0, String::from("SelectBox(Yes, No)")),
+            (1, String::from("Button(OK)")),
+            (2, String::from("Checkbox(Remember me)")),
+        ]
+    );
+}
+
+// Widgets can overlap, and whatever is drawn last ends up on top. run_sorted draws the components in ascending z_order
+// rather than in the order they were added. The Box<dyn Draw> values can't be compared with each other directly, so we
+// collect references to them and use sort_by_key with each one's z_order as the key. The sort is stable, so components with
+// the same z_order keep the order they were added in. Like run_indexed, it returns what it drew, in the order it drew it:
+impl Button {
+    pub fn with_z(mut self, z: i32) -> Button {
+        self.z = z;
+        self
+    }
+}
+
+impl Screen {
+    pub fn run_sorted(&self) -> Vec<String> {
+        let mut components: Vec<&dyn Draw> = self.components.iter().map(|component| component.as_ref()).collect();
+        components.sort_by_key(|component| component.z_order());
+
+        components
+            .into_iter()
+            .map(|component| {
+                component.draw();
+                component.describe()
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn run_a_screen_in_z_order() {
+    let screen = Screen {
+        components: vec![
+            Box::new(Button::new("Top").with_z(10)),
+            Box::new(Button::new("Bottom").with_z(-5)),
+            Box::new(SelectBox {
+                width: 75,
+                height: 10,
+                options: vec![String::from("Yes"), String::from("No")],
+                selected: None,
+            }),
+            Box::new(Button::new("Also middle")),
+        ],
+    };
+
+    assert_eq!(
+        screen.run_sorted(),
+        vec![
+            String::from("Button(Bottom)"),
+            String::from("SelectBox(Yes, No)"),
+            String::from("Button(Also middle)"),
+            String::from("Button(Top)"),
+        ]
+    );
+}
+
+// Trait objects also make the decorator pattern easy. Bordered wraps any widget that implements Draw and is itself a Draw,
+// so it can go anywhere the original could. It forwards everything to the inner widget, except that its bounds grow by the
+// border's thickness on every side and its ASCII rendering draws that border around the inner widget's lines:
+pub struct Bordered<T: Draw> {
+    pub inner: T,
+    pub thickness: u32,
+}
+
+impl<T: Draw + 'static> Draw for Bordered<T> {
+    fn draw(&self) {
+        // code to draw the border, then the widget inside it
+        self.inner.draw();
+    }
+
+    fn as_clickable(&self) -> Option<&dyn Clickable> {
+        self.inner.as_clickable()
+    }
+
+    fn bounds(&self) -> (u32, u32) {
+        let (width, height) = self.inner.bounds();
+        (width + 2 * self.thickness, height + 2 * self.thickness)
+    }
+
+    fn describe(&self) -> String {
+        format!("Bordered({})", self.inner.describe())
+    }
+
+    fn z_order(&self) -> i32 {
+        self.inner.z_order()
+    }
+
+    fn render_ascii(&self) -> Vec<String> {
+        let lines = self.inner.render_ascii();
+        if lines.is_empty() {
+            return lines;
+        }
+
+        let thickness = self.thickness as usize;
+        let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let edge = "#".repeat(width + 2 * thickness);
+        let side = "#".repeat(thickness);
+
+        let mut rendered = vec![edge.clone(); thickness];
+        rendered.extend(lines.iter().map(|line| format!("{}{:<width$}{}", side, line, side, width = width)));
+        rendered.extend(vec![edge; thickness]);
+        rendered
+    }
+}
+
+#[test]
+fn bordered_button_reports_expanded_bounds() {
+    let bordered = Bordered {
+        inner: Button::new("OK"),
+        thickness: 2,
+    };
+
+    assert_eq!(bordered.bounds(), (54, 14));
+    assert_eq!(bordered.describe(), "Bordered(Button(OK))");
+
+    let screen = Screen {
+        components: vec![Box::new(bordered)],
+    };
+    assert_eq!(screen.click_at(0), Some(String::from("OK")));
+}
+
+// A Vec is fine when components are only ever drawn in order, but sometimes a widget needs to be looked up by name. The
+// values of a HashMap can be trait objects too, so DrawRegistry maps names to any boxed Draw widget:
+use std::collections::HashMap;
+
+pub struct DrawRegistry {
+    widgets: HashMap<String, Box<dyn Draw>>,
+}
+
+impl DrawRegistry {
+    pub fn new() -> DrawRegistry {
+        DrawRegistry {
+            widgets: HashMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, name: impl Into<String>, widget: Box<dyn Draw>) {
+        self.widgets.insert(name.into(), widget);
+    }
+
+    pub fn draw_named(&self, name: &str) -> bool {
+        match self.widgets.get(name) {
+            Some(widget) => {
+                widget.draw();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[test]
+fn draw_widgets_by_name() {
+    let mut registry = DrawRegistry::new();
+    registry.register("ok", Box::new(Button::new("OK")));
+    registry.register(
+        String::from("answer"),
+        Box::new(SelectBox {
+            width: 75,
+            height: 10,
+            options: vec![String::from("Yes"), String::from("No")],
+            selected: None,
+        }),
+    );
+
+    assert!(registry.draw_named("ok"));
+    assert!(registry.draw_named("answer"));
+    assert!(!registry.draw_named("cancel"));
+}
+
+// None of the draw methods actually put anything on screen, which makes them hard to check. render_ascii gives a widget a
+// way to show what it would look like as plain text. Button draws a box of width columns and height rows with its label
+// centered on the middle row. Widgets that haven't been taught how to render keep the default and produce no lines:
+#[test]
+fn render_a_button_as_ascii() {
+    let button = Button::new("OK").with_size(8, 3);
+
+    assert_eq!(
+        button.render_ascii(),
+        vec![
+            String::from("+------+"),
+            String::from("|  OK  |"),
+            String::from("+------+"),
+        ]
+    );
+}
+
+#[test]
+fn render_a_tall_button_with_a_long_label() {
+    let button = Button::new("Cancel").with_size(6, 5);
+
+    assert_eq!(
+        button.render_ascii(),
+        vec![
+            String::from("+----+"),
+            String::from("|    |"),
+            String::from("|C...|"),
+            String::from("|    |"),
+            String::from("+----+"),
+        ]
+    );
+    assert!(Button::new("OK").with_size(1, 1).render_ascii().is_empty());
+    assert!(Checkbox {
+        label: String::from("Remember me"),
+        checked: Cell::new(false),
+    }
+    .render_ascii()
+    .is_empty());
+}
+
+// Bordered renders its inner widget surrounded by thickness rows and columns of #, so the border shows up in the text too.
+// A widget that renders nothing stays empty rather than becoming a box around nothing:
+#[test]
+fn render_a_bordered_button_as_ascii() {
+    let bordered = Bordered {
+        inner: Button::new("OK").with_size(4, 3),
+        thickness: 2,
+    };
+
+    assert_eq!(
+        bordered.render_ascii(),
+        vec![
+            String::from("########"),
+            String::from("########"),
+            String::from("##+--+##"),
+            String::from("##|OK|##"),
+            String::from("##+--+##"),
+            String::from("########"),
+            String::from("########"),
+        ]
+    );
+    assert!(Bordered {
+        inner: Button::new("OK").with_size(1, 1),
+        thickness: 2,
+    }
+    .render_ascii()
+    .is_empty());
+}
+
+// A label longer than the space inside the border would push the border out and break the box. display_label fits the
+// label into the width - 2 columns between the borders, swapping the end of a label that's too long for "..." so the
+// reader can tell it was cut. It counts and cuts chars rather than bytes, so a label with multibyte characters is never
+// split in the middle of one:
+impl Button {
+    pub fn display_label(&self) -> String {
+        let room = (self.width as usize).saturating_sub(2);
+        if self.label.chars().count() <= room {
+            return self.label.clone();
+        }
+        if room < 3 {
+            return self.label.chars().take(room).collect();
+        }
+
+        let mut label: String = self.label.chars().take(room - 3).collect();
+        label.push_str("...");
+        label
+    }
+}
+
+#[test]
+fn display_label_that_fits() {
+    assert_eq!(Button::new("OK").with_size(8, 3).display_label(), "OK");
+    assert_eq!(Button::new("Cancel").with_size(8, 3).display_label(), "Cancel");
+}
+
+#[test]
+fn display_label_that_is_too_long() {
+    assert_eq!(Button::new("Cancel").with_size(7, 3).display_label(), "Ca...");
+    assert_eq!(Button::new("Ünïcödé").with_size(7, 3).display_label(), "Ün...");
+    assert_eq!(Button::new("Cancel").with_size(4, 3).display_label(), "Ca");
+    assert_eq!(
+        Button::new("Cancel").with_size(7, 3).render_ascii(),
+        vec![
+            String::from("+-----+"),
+            String::from("|Ca...|"),
+            String::from("+-----+"),
+        ]
+    );
+}
+
+// With render_ascii we can check a whole screen at once. snapshot renders every component in order and joins the lines of
+// each one together, leaving a blank line between components. The same screen always gives the same string, so a test
+// can compare it against a snapshot written out by hand and catch any change in how the screen looks:
+pub fn snapshot(screen: &Screen) -> String {
+    screen
+        .components
+        .iter()
+        .map(|component| component.render_ascii().join("\n"))
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+#[test]
+fn snapshot_a_screen() {
+    let screen = Screen {
+        components: vec![
+            Box::new(Button::new("OK").with_size(6, 3)),
+            Box::new(Button::new("Cancel").with_size(10, 4)),
+        ],
+    };
+
+    assert_eq!(
+        snapshot(&screen),
+        "+----+\n\
+         | OK |\n\
+         +----+\n\
+         \n\
+         +--------+\n\
+         | Cancel |\n\
+         |        |\n\
+         +--------+"
+    );
+}
+
+// components is public, but a dynamic UI shouldn't have to reach into the Vec and risk a panic on a bad index. remove
+// takes the component at index out of the screen and hands it back, or returns None if there isn't one there. move_to
+// takes the component at from out and puts it back in at to, shifting the ones in between over by one; it checks both
+// indices first and reports the one that's out of range, leaving the screen as it was:
+impl Screen {
+    pub fn remove(&mut self, index: usize) -> Option<Box<dyn Draw>> {
+        if index < self.components.len() {
+            Some(self.components.remove(index))
+        } else {
+            None
+        }
+    }
+
+    pub fn move_to(&mut self, from: usize, to: usize) -> Result<(), String> {
+        let len = self.components.len();
+        for &index in &[from, to] {
+            if index >= len {
+                return Err(format!("index {} is out of range for a screen with {} components", index, len));
+            }
+        }
+
+        let component = self.components.remove(from);
+        self.components.insert(to, component);
+        Ok(())
+    }
+}
+
+#[test]
+fn remove_a_component_from_a_screen() {
+    let mut screen = Screen {
+        components: vec![Box::new(Button::new("OK")), Box::new(Button::new("Cancel"))],
+    };
+
+    let removed = screen.remove(0).unwrap();
+
+    assert_eq!(removed.describe(), "Button(OK)");
+    assert_eq!(screen.components.len(), 1);
+    assert!(screen.remove(1).is_none());
+}
+
+#[test]
+fn reorder_components_on_a_screen() {
+    let mut screen = Screen {
+        components: vec![
+            Box::new(Button::new("OK")),
+            Box::new(Button::new("Cancel")),
+            Box::new(Button::new("Help")),
+        ],
+    };
+    let order = |screen: &Screen| -> Vec<String> {
+        screen.components.iter().map(|component| component.describe()).collect()
+    };
+
+    assert_eq!(screen.move_to(0, 2), Ok(()));
+    assert_eq!(order(&screen), vec!["Button(Cancel)", "Button(Help)", "Button(OK)"]);
+
+    assert_eq!(screen.move_to(2, 1), Ok(()));
+    assert_eq!(order(&screen), vec!["Button(Cancel)", "Button(OK)", "Button(Help)"]);
+
+    assert_eq!(
+        screen.move_to(1, 3),
+        Err(String::from("index 3 is out of range for a screen with 3 components"))
+    );
+    assert_eq!(order(&screen), vec!["Button(Cancel)", "Button(OK)", "Button(Help)"]);
+}
+
+// Once a component is in a Screen, all we know about it is that it implements Draw. as_any turns a &dyn Draw into a
+// &dyn Any, and Any's downcast_ref checks at runtime whether the value really is the type we ask for, giving back
+// Some(&Button) if it is and None if it isn't. get_button uses that to hand back a Button so its fields can be read. The
+// component has to be dereferenced to the dyn Draw first: as_any called on the Box itself would hand back the Box:
+impl Screen {
+    pub fn get_button(&self, index: usize) -> Option<&Button> {
+        (**self.components.get(index)?).as_any().downcast_ref::<Button>()
+    }
+}
+
+#[test]
+fn get_button_downcasts_only_buttons() {
+    let screen = Screen {
+        components: vec![
+            Box::new(Button::new("OK").with_size(80, 20)),
+            Box::new(SelectBox {
+                width: 75,
+                height: 10,
+                options: vec![String::from("Yes"), String::from("No")],
+                selected: None,
+            }),
+        ],
+    };
+
+    let button = screen.get_button(0).unwrap();
+    assert_eq!(button.label, "OK");
+    assert_eq!(button.width, 80);
+
+    assert!(screen.get_button(1).is_none());
+    assert!((*screen.components[1]).as_any().downcast_ref::<SelectBox>().is_some());
+    assert!(screen.get_button(2).is_none());
+}
+
+// run only ever calls draw, so every other thing we want to do with each component (collect its bounds, render it, log
+// it) would need another method on Screen. for_each hands each component to a closure instead and lets the caller decide.
+// The closure gets a &dyn Draw, so it can call any of the Draw methods:
+impl Screen {
+    pub fn for_each<F: FnMut(&dyn Draw)>(&self, mut f: F) {
+        for component in self.components.iter() {
+            f(component.as_ref());
+        }
+    }
+}
+
+#[test]
+fn for_each_sums_component_heights() {
+    let screen = Screen {
+        components: vec![
+            Box::new(Button::new("OK").with_size(50, 10)),
+            Box::new(SelectBox {
+                width: 75,
+                height: 25,
+                options: vec![String::from("Yes"), String::from("No")],
+                selected: None,
+            }),
+            Box::new(Button::new("Cancel").with_size(50, 15)),
+        ],
+    };
+
+    let mut total_height = 0;
+    screen.for_each(|component| total_height += component.bounds().1);
+
+    assert_eq!(total_height, 50);
+}
+
+// Because a Screen holds Box<dyn Draw>, the components don't have to be known when the code is written; they can come
+// from a config file. build_screen reads one widget per line: its kind, its size as WIDTHxHEIGHT, and then the rest of
+// the line, which is a button's label or a select box's comma-separated options. Blank lines are skipped. The first line
+// that can't be read stops the build, and the error gives that line's number and text:
+pub fn build_screen(spec: &str) -> Result<Screen, String> {
+    let mut components: Vec<Box<dyn Draw>> = Vec::new();
+
+    for (i, line) in spec.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let fail = |reason: &str| format!("line {}: {} in {:?}", i + 1, reason, line);
+        let mut parts = line.splitn(3, ' ');
+        let kind = parts.next().unwrap_or("");
+        let size = parts.next().ok_or_else(|| fail("missing size"))?;
+        let rest = parts.next().unwrap_or("").trim();
+
+        let (width, height) = size
+            .split_once('x')
+            .and_then(|(width, height)| Some((width.parse::<u32>().ok()?, height.parse::<u32>().ok()?)))
+            .ok_or_else(|| fail("size must look like 50x10"))?;
+
+        match kind {
+            "button" => components.push(Box::new(Button::new(rest).with_size(width, height))),
+            "select" => components.push(Box::new(SelectBox {
+                width,
+                height,
+                options: rest.split(',').map(|option| String::from(option.trim())).collect(),
+                selected: None,
+            })),
+            _ => return Err(fail("unknown widget")),
+        }
+    }
+
+    Ok(Screen { components })
+}
+
+#[test]
+fn build_a_screen_from_a_spec() {
+    let screen = build_screen("button 50x10 OK\nselect 75x10 Yes,Maybe,No\n\nbutton 80x20 Sign in").unwrap();
+
+    assert_eq!(screen.components.len(), 3);
+    assert_eq!(screen.get_button(0).unwrap().label, "OK");
+    assert_eq!(screen.components[1].describe(), "SelectBox(Yes, Maybe, No)");
+    assert_eq!(screen.components[1].bounds(), (75, 10));
+    assert_eq!(screen.get_button(2).unwrap().label, "Sign in");
+    assert_eq!(screen.components[2].bounds(), (80, 20));
+}
+
+#[test]
+fn build_screen_reports_the_bad_line() {
+    let err = build_screen("button 50x10 OK\nbutton 50xten Cancel").err().unwrap();
+    assert_eq!(err, "line 2: size must look like 50x10 in \"button 50xten Cancel\"");
+
+    assert_eq!(build_screen("slider 10x10 volume").err().unwrap(), "line 1: unknown widget in \"slider 10x10 volume\"");
+    assert_eq!(build_screen("button").err().unwrap(), "line 1: missing size in \"button\"");
+}
+
+// snapshot builds the whole screen as one String, which is fine for a test but not for a real terminal or file. render_to
+// writes each line of every component's render_ascii straight to anything that implements io::Write, ending every line
+// with a newline. Components that don't render as ASCII write nothing, and the first write that fails stops the render
+// and returns its error:
+impl Screen {
+    pub fn render_to<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        for component in self.components.iter() {
+            for line in component.render_ascii() {
+                writeln!(w, "{}", line)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn render_a_screen_into_a_byte_buffer() {
+    let screen = Screen {
+        components: vec![
+            Box::new(Button::new("OK").with_size(6, 3)),
+            Box::new(SelectBox {
+                width: 75,
+                height: 10,
+                options: vec![String::from("Yes"), String::from("No")],
+                selected: None,
+            }),
+            Box::new(Button::new("No").with_size(4, 3)),
+        ],
+    };
+    let mut out = Vec::new();
+
+    screen.render_to(&mut out).unwrap();
+
+    assert_eq!(out, b"+----+\n| OK |\n+----+\n+--+\n|No|\n+--+\n".to_vec());
+}
+
+// A Vec grows by reallocating: when it runs out of room it allocates a bigger buffer and moves every element over. A
+// screen built up one component at a time in a loop can go through several of those. When the number of components is
+// known ahead of time, with_capacity makes room for all of them up front, and add pushes onto the Vec without touching
+// the capacity until more than n components have been added:
+impl Screen {
+    pub fn with_capacity(n: usize) -> Screen {
+        Screen {
+            components: Vec::with_capacity(n),
+        }
+    }
+
+    pub fn add(&mut self, component: Box<dyn Draw>) {
+        self.components.push(component);
+    }
+}
+
+#[test]
+fn adding_up_to_the_capacity_does_not_reallocate() {
+    let mut screen = Screen::with_capacity(100);
+    let capacity = screen.components.capacity();
+    assert!(capacity >= 100);
+
+    for i in 0..100 {
+        screen.add(Box::new(Button::new(format!("Button {}", i))));
+        assert_eq!(screen.components.capacity(), capacity);
+    }
+
+    assert_eq!(screen.components.len(), 100);
+    assert_eq!(screen.get_button(99).unwrap().label, "Button 99");
+}
+
 // Trait Objects Perform Dynamic Dispatch
 // Recall in the “Performance of Code Using Generics” section in Chapter 10 our discussion on the monomorphization
 // process performed by the compiler when we use trait bounds on generics: the compiler generates nongeneric implementations
@@ -375,13 +1465,19 @@ fn main() {
 pub struct Post {
     state: Option<Box<dyn State>>,
     content: String,
+    // The name of every state the post has been in, starting with draft
+    history: Vec<&'static str>,
+    // Callbacks registered with on_transition, each called with the name of every new state
+    observers: RefCell<Vec<Observer>>,
 }
 
 impl Post {
     pub fn new() -> Post {
         Post {
             state: Some(Box::new(Draft{})),
-            content: String::new();
+            content: String::new(),
+            history: vec!["draft"],
+            observers: RefCell::new(Vec::new()),
         }
     }
 
@@ -397,21 +1493,72 @@ impl Post {
         if let Some(s) = self.state.take() {
             self.state = Some(s.request_review())
         }
+        self.record_transition();
     }
 
     pub fn approve(&mut self) {
         if let Some(s) = self.state.take() {
             self.state = Some(s.approve())
         }
+        self.record_transition();
+    }
+
+    pub fn transition_history(&self) -> &[&'static str] {
+        &self.history
+    }
+
+    // A transition that leaves the post where it was, like approving a draft, isn't a new entry
+    fn record_transition(&mut self) {
+        let name = self.state_name();
+
+        if self.history.last() != Some(&name) {
+            self.history.push(name);
+
+            // Take the observers out while calling them so none of them is running under a borrow of the RefCell, then put
+            // them back in front of any that were registered in the meantime
+            let observers = std::mem::take(&mut *self.observers.borrow_mut());
+            for observer in &observers {
+                observer(name);
+            }
+            let added = std::mem::replace(&mut *self.observers.borrow_mut(), observers);
+            self.observers.borrow_mut().extend(added);
+        }
+    }
+
+    pub fn state_name(&self) -> &'static str {
+        self.state.as_ref().unwrap().name()
+    }
+
+    // Counting words doesn't reveal the content, so unlike content this works in every state
+    pub fn word_count(&self) -> usize {
+        count_words(&self.content)
+    }
+
+    // Built on content rather than the field, so a post that isn't published yet previews as an empty string too
+    pub fn preview(&self, words: usize) -> String {
+        self.content()
+            .split_whitespace()
+            .take(words)
+            .collect::<Vec<&str>>()
+            .join(" ")
     }
 }
 
+// Words are whatever split_whitespace separates, so runs of spaces, tabs, and newlines between them don't add empty words
+fn count_words(content: &str) -> usize {
+    content.split_whitespace().count()
+}
+
 trait State {
     fn request_review(self: Box<Self>) -> Box<dyn State>;
     fn approve(self: Box<Self>) -> Box<dyn State>;
     fn content<'a>(&self, post: &'a Post) -> &'a str {
         ""
     }
+    // A short, fixed name for the state so tests and logs can see which one a post is in
+    fn name(&self) -> &'static str;
+    // Clone can't be a supertrait of a trait used as dyn State, so each state boxes up a copy of itself instead
+    fn clone_box(&self) -> Box<dyn State>;
 }
 
 struct Draft {}
@@ -424,6 +1571,14 @@ impl State for Draft {
     fn approve(self: Box<Self>) -> Box<dyn State> {
         self
     }
+
+    fn name(&self) -> &'static str {
+        "draft"
+    }
+
+    fn clone_box(&self) -> Box<dyn State> {
+        Box::new(Draft {})
+    }
 }
 
 struct PendingReview {}
@@ -436,6 +1591,14 @@ impl State for PendingReview {
     fn approve(self: Box<Self>) -> Box<dyn State> {
         Box::new(Published {})
     }
+
+    fn name(&self) -> &'static str {
+        "pending_review"
+    }
+
+    fn clone_box(&self) -> Box<dyn State> {
+        Box::new(PendingReview {})
+    }
 }
 
 struct Published {}
@@ -452,6 +1615,143 @@ impl State for Published {
     fn content<'a>(&self, post: &'a Post) -> &'a str {
         &post.content
     }
+
+    fn name(&self) -> &'static str {
+        "published"
+    }
+
+    fn clone_box(&self) -> Box<dyn State> {
+        Box::new(Published {})
+    }
+}
+
+// Because the states are hidden behind Box<dyn State>, a test can't tell which one a Post is in from the outside. The
+// name method on State lets Post report it through state_name:
+#[test]
+fn state_name_follows_each_transition() {
+    let mut post = Post::new();
+    assert_eq!(post.state_name(), "draft");
+
+    post.approve();
+    assert_eq!(post.state_name(), "draft");
+
+    post.request_review();
+    assert_eq!(post.state_name(), "pending_review");
+
+    post.approve();
+    assert_eq!(post.state_name(), "published");
+}
+
+#[test]
+fn word_count_in_every_state() {
+    let mut post = Post::new();
+    post.add_text("I ate a salad");
+    post.add_text(" for lunch today");
+    assert_eq!(post.content(), "");
+    assert_eq!(post.word_count(), 7);
+
+    post.request_review();
+    assert_eq!(post.word_count(), 7);
+
+    post.approve();
+    assert_eq!(post.word_count(), 7);
+}
+
+#[test]
+fn transition_history_records_each_state_entered() {
+    let mut post = Post::new();
+    assert_eq!(post.transition_history(), &["draft"]);
+
+    post.approve();
+    post.request_review();
+    post.request_review();
+    post.approve();
+
+    assert_eq!(post.transition_history(), &["draft", "pending_review", "published"]);
+}
+
+#[test]
+fn preview_only_after_publishing() {
+    let mut post = Post::new();
+    post.add_text("I ate a salad\nfor  lunch today");
+    assert_eq!(post.preview(3), "");
+
+    post.request_review();
+    assert_eq!(post.preview(3), "");
+
+    post.approve();
+    assert_eq!(post.preview(3), "I ate a");
+    assert_eq!(post.preview(5), "I ate a salad for");
+    assert_eq!(post.preview(10), "I ate a salad for lunch today");
+    assert_eq!(post.preview(0), "");
+}
+
+// With clone_box, Post can implement Clone by cloning its content and history and asking its state for a boxed copy of
+// itself. The clone is a separate post from then on, so approving one doesn't move the other along. Boxed closures can't
+// be cloned, so the copy starts out with no observers:
+impl Clone for Post {
+    fn clone(&self) -> Post {
+        Post {
+            state: self.state.as_ref().map(|state| state.clone_box()),
+            content: self.content.clone(),
+            history: self.history.clone(),
+            observers: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+#[test]
+fn cloned_post_transitions_independently() {
+    let mut post = Post::new();
+    post.add_text("I ate a salad for lunch today");
+    post.request_review();
+
+    let mut copy = post.clone();
+    assert_eq!(copy.state_name(), "pending_review");
+    assert_eq!(copy.transition_history(), &["draft", "pending_review"]);
+
+    copy.approve();
+    assert_eq!(copy.content(), "I ate a salad for lunch today");
+    assert_eq!(post.state_name(), "pending_review");
+    assert_eq!(post.content(), "");
+
+    post.add_text(" and then");
+    assert_eq!(copy.word_count(), 7);
+    assert_eq!(post.word_count(), 9);
+}
+
+// Code outside the post can't see its state change, only ask for state_name afterwards. on_transition lets callers react
+// as it happens instead: record_transition calls every registered observer with the name of the state the post just
+// moved into, at the same point it adds that name to the history, so a transition that leaves the post where it was
+// doesn't notify anyone. The observers sit in a RefCell so one can be registered through &self, even while the post is
+// shared:
+use std::cell::RefCell;
+
+type Observer = Box<dyn Fn(&str)>;
+
+impl Post {
+    pub fn on_transition(&self, cb: Observer) {
+        self.observers.borrow_mut().push(cb);
+    }
+}
+
+#[test]
+fn observers_hear_each_transition() {
+    use std::rc::Rc;
+
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let mut post = Post::new();
+
+    let log = Rc::clone(&seen);
+    post.on_transition(Box::new(move |name| log.borrow_mut().push(name.to_string())));
+
+    post.approve();
+    post.request_review();
+    post.request_review();
+    post.approve();
+
+    assert_eq!(*seen.borrow(), vec!["pending_review", "published"]);
+    assert_eq!(post.clone().observers.borrow().len(), 0);
 }
 
 // Encoding States and Behavior as Types
@@ -531,4 +1831,38 @@ fn main() {
     let post = post.approve();
 
     assert_eq!("I ate a salad for lunch today", post.content())
-}
\ No newline at end of file
+}
+
+// Each of the post types can still offer things that are safe in its state. Word counts don't expose the content itself,
+// so all three types get a word_count method, counting with the same count_words helper as the state-pattern Post:
+
+impl DraftPost {
+    pub fn word_count(&self) -> usize {
+        count_words(&self.content)
+    }
+}
+
+impl PendingReviewPost {
+    pub fn word_count(&self) -> usize {
+        count_words(&self.content)
+    }
+}
+
+impl Post {
+    pub fn word_count(&self) -> usize {
+        count_words(&self.content)
+    }
+}
+
+#[test]
+fn word_count_for_each_post_type() {
+    let mut post = Post::new();
+    post.add_text("I ate a salad for lunch today");
+    assert_eq!(post.word_count(), 7);
+
+    let post = post.request_review();
+    assert_eq!(post.word_count(), 7);
+
+    let post = post.approve();
+    assert_eq!(post.word_count(), 7);
+}
@@ -79,6 +79,45 @@ while let Some(top) = stack.pop() {
 // the code in its block as long as pop returns Some. When pop returns None, the loop stops. We can
 // use while let to pop every element off our stack.
 
+// We can give that stack its own type. Stack<T> only allows pushing onto and popping off the top, and implementing
+// Iterator with pop as next means a for loop drains it in the same last-in, first-out order as the while let above:
+struct Stack<T> {
+    items: Vec<T>,
+}
+
+impl<T> Stack<T> {
+    fn new() -> Stack<T> {
+        Stack { items: Vec::new() }
+    }
+
+    fn push(&mut self, item: T) {
+        self.items.push(item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.items.pop()
+    }
+}
+
+impl<T> Iterator for Stack<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.pop()
+    }
+}
+
+#[test]
+fn drain_a_stack_in_lifo_order() {
+    let mut stack = Stack::new();
+
+    stack.push(1);
+    stack.push(2);
+    stack.push(3);
+
+    assert_eq!(stack.collect::<Vec<i32>>(), vec![3, 2, 1]);
+}
+
 // Listing 18-3 demonstrates how to use a pattern in a for loop to destructure, or break apart, a
 // tuple as part of the for loop.
 let v = vec!['a', 'b', 'c'];
@@ -228,13 +267,13 @@ match x {
     _ => println!("anything"),
 }
 
-// Matching Ranges of Values with ...
-// The ... syntax allows us to match to an inclusive range of values. In the following code, when a pattern matches
+// Matching Ranges of Values with ..=
+// The ..= syntax allows us to match to an inclusive range of values. In the following code, when a pattern matches
 // any of the values within the range, that arm will execute:
 let x = 5;
 
 match x {
-    1...5 => println!("one through five"),
+    1..=5 => println!("one through five"),
     _ => println("something else"),
 }
 
@@ -242,11 +281,38 @@ match x {
 let x = 'c';
 
 match x {
-    'a'...'j' => println!("early ASCII letter"),
-    'k'...'z' => println!("late ASCII letter"),
+    'a'..='j' => println!("early ASCII letter"),
+    'k'..='z' => println!("late ASCII letter"),
     _ => println!("something else"),
 }
 
+// The same char ranges make a small classifier if we return an enum instead of printing. Ranges are checked
+// in order like any other arms, so digits get their own '0'..='9' arm and everything else falls to _:
+#[derive(Debug, PartialEq)]
+enum CharClass {
+    EarlyLetter,
+    LateLetter,
+    Digit,
+    Other,
+}
+
+fn classify_char(c: char) -> CharClass {
+    match c {
+        'a'..='j' => CharClass::EarlyLetter,
+        'k'..='z' => CharClass::LateLetter,
+        '0'..='9' => CharClass::Digit,
+        _ => CharClass::Other,
+    }
+}
+
+#[test]
+fn classifies_chars_by_range() {
+    assert_eq!(classify_char('c'), CharClass::EarlyLetter);
+    assert_eq!(classify_char('x'), CharClass::LateLetter);
+    assert_eq!(classify_char('5'), CharClass::Digit);
+    assert_eq!(classify_char('#'), CharClass::Other);
+}
+
 // Destructuring to Break Apart Values
 struct Point {
     x: i32,
@@ -331,6 +397,49 @@ fn main() {
 // pattern we specify to match tuples. The number of variables in the pattern must match the number
 // of elements in the variant we’re matching.
 
+// Because every arm destructures its variant, the same match can drive a tiny command processor. Below,
+// apply folds one Message into a (x, y, (r, g, b)) state: Move shifts the position, ChangeColor replaces
+// the color, Write carries no state so it is just echoed, and Quit returns false to tell the caller to stop.
+fn apply(msg: Message, state: &mut (i32, i32, (i32, i32, i32))) -> bool {
+    match msg {
+        Message::Quit => false,
+        Message::Move { x, y } => {
+            state.0 += x;
+            state.1 += y;
+            true
+        },
+        Message::Write(text) => {
+            println!("Text message: {}", text);
+            true
+        },
+        Message::ChangeColor(r, g, b) => {
+            state.2 = (r, g, b);
+            true
+        },
+    }
+}
+
+#[test]
+fn apply_runs_messages_until_quit() {
+    let messages = vec![
+        Message::Move { x: 3, y: 4 },
+        Message::Write(String::from("hello")),
+        Message::ChangeColor(0, 160, 255),
+        Message::Move { x: -1, y: 1 },
+        Message::Quit,
+        Message::Move { x: 100, y: 100 },
+    ];
+    let mut state = (0, 0, (0, 0, 0));
+
+    for msg in messages {
+        if !apply(msg, &mut state) {
+            break;
+        }
+    }
+
+    assert_eq!(state, (2, 5, (0, 160, 255)));
+}
+
 // Destructuring Nested Structs and Enums
 // Until now, all our examples have been matching structs or enums that were one level deep. Matching can work
 // on nested items too!
@@ -460,6 +569,112 @@ fn main() {
     }
 }
 
+// The same .. works inside slice patterns, which makes argument parsing read like the shape of the arguments
+// themselves. The first element of env::args is the program name, so we skip it with _, bind the next two, and
+// let .. swallow anything extra. Any slice shorter than that falls through to the second arm:
+struct Config {
+    query: String,
+    path: String,
+}
+
+impl Config {
+    fn build(args: &[String]) -> Result<Config, &'static str> {
+        match args {
+            [_, query, path, ..] => Ok(Config {
+                query: query.clone(),
+                path: path.clone(),
+            }),
+            _ => Err("not enough arguments"),
+        }
+    }
+}
+
+#[test]
+fn build_config_from_args() {
+    let args = vec![
+        String::from("minigrep"),
+        String::from("duct"),
+        String::from("poem.txt"),
+    ];
+    let config = Config::build(&args).unwrap();
+
+    assert_eq!(config.query, "duct");
+    assert_eq!(config.path, "poem.txt");
+}
+
+#[test]
+fn build_config_with_too_few_args() {
+    let args = vec![String::from("minigrep"), String::from("duct")];
+
+    assert_eq!(Config::build(&args).err(), Some("not enough arguments"));
+}
+
+// Arguments don't have to be the only source, either. from_args_or_env layers the environment underneath them: each value
+// comes from its argument when one was given and from the QUERY or FILE_PATH variable otherwise. The lookup is passed in as
+// a closure so the layering can be tested without changing the real process environment, which all tests share:
+use std::env;
+
+impl Config {
+    fn from_args_or_env(args: &[String]) -> Result<Config, String> {
+        Config::from_args_or_lookup(args, |key| env::var(key).ok())
+    }
+
+    fn from_args_or_lookup<F>(args: &[String], lookup: F) -> Result<Config, String>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let value = |index: usize, key: &str, name: &str| match args.get(index) {
+            Some(arg) => Ok(arg.clone()),
+            None => lookup(key).ok_or(format!(
+                "no {} given: pass it as argument {} or set {}",
+                name, index, key
+            )),
+        };
+
+        Ok(Config {
+            query: value(1, "QUERY", "query")?,
+            path: value(2, "FILE_PATH", "file path")?,
+        })
+    }
+}
+
+#[test]
+fn config_prefers_args_over_env() {
+    let args = vec![
+        String::from("minigrep"),
+        String::from("duct"),
+        String::from("poem.txt"),
+    ];
+    let config = Config::from_args_or_lookup(&args, |_| Some(String::from("from env"))).unwrap();
+
+    assert_eq!(config.query, "duct");
+    assert_eq!(config.path, "poem.txt");
+}
+
+#[test]
+fn config_falls_back_to_env() {
+    let args = vec![String::from("minigrep")];
+    let config = Config::from_args_or_lookup(&args, |key| match key {
+        "QUERY" => Some(String::from("to")),
+        "FILE_PATH" => Some(String::from("poem.txt")),
+        _ => None,
+    })
+    .unwrap();
+
+    assert_eq!(config.query, "to");
+    assert_eq!(config.path, "poem.txt");
+}
+
+#[test]
+fn config_with_neither_args_nor_env() {
+    let args = vec![String::from("minigrep")];
+
+    assert_eq!(
+        Config::from_args_or_lookup(&args, |_| None).err(),
+        Some(String::from("no query given: pass it as argument 1 or set QUERY"))
+    );
+}
+
 // Extra Conditionals with Match Guards
 // A match guard is an additional if condition specified after the pattern in a match arm that must also match, along
 // with the pattern matching, for that arm to be chosen. Match guards are useful for expressing more complex ideas
@@ -496,6 +711,35 @@ fn main() {
 // The match guard if n == y is not a pattern and therefore doesn’t introduce new variables. This y is the outer y rather than
 // a new shadowed y, and we can look for a value that has the same value as the outer y by comparing n to y.
 
+// The same idea makes removing consecutive duplicates short. dedup_adjacent matches on the last value it has kept so far,
+// and the guard compares it to the current item; only when the guard fails does the catch-all arm keep the item. The first
+// item of every run is kept, empty input gives an empty Vec, and input that is all one value collapses to a single item:
+fn dedup_adjacent<T: PartialEq + Clone>(items: &[T]) -> Vec<T> {
+    let mut result: Vec<T> = Vec::new();
+
+    for item in items {
+        match result.last() {
+            Some(last) if last == item => (),
+            _ => result.push(item.clone()),
+        }
+    }
+
+    result
+}
+
+#[test]
+fn dedup_adjacent_runs() {
+    assert_eq!(dedup_adjacent(&[1, 1, 2, 3, 3, 3, 1]), vec![1, 2, 3, 1]);
+    assert_eq!(dedup_adjacent(&["a", "a", "a"]), vec!["a"]);
+}
+
+#[test]
+fn dedup_adjacent_empty() {
+    let empty: [i32; 0] = [];
+
+    assert_eq!(dedup_adjacent(&empty), Vec::<i32>::new());
+}
+
 // You can also use the or operator | in a match guard to specify multiple patterns; the match guard condition will apply to
 // all the patterns. Listing 18-28 shows the precedence of combining a match guard with a pattern that uses |. The important
 // part of this example is that the if y match guard applies to 4, 5, and 6, even though it might look like if y only applies
@@ -88,6 +88,7 @@ fn main() {
 
 // We can change the definition of the List enum from before and the usage of the List from before to the
 // code below, which will compile:
+#[derive(Debug, PartialEq)]
 enum List {
     Cons(i32, Box<List>),
     Nil,
@@ -102,6 +103,700 @@ fn main() {
                 Box::new(Nil))))));
 }
 
+// Now that the list has a known size we can write real algorithms on it. reverse consumes the list and rebuilds it
+// back to front. It walks the nodes with a while let loop rather than recursing, so a very long list can't overflow
+// the stack: each step moves the value out of the current Cons, pushes it onto the front of the reversed list, and
+// moves on to the unboxed next node.
+impl List {
+    fn reverse(self) -> List {
+        let mut reversed = Nil;
+        let mut current = self;
+
+        while let Cons(value, next) = current {
+            reversed = Cons(value, Box::new(reversed));
+            current = *next;
+        }
+
+        reversed
+    }
+}
+
+#[test]
+fn reverse_a_list() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Cons(4, Box::new(Nil))))))));
+
+    assert_eq!(
+        list.reverse(),
+        Cons(4, Box::new(Cons(3, Box::new(Cons(2, Box::new(Cons(1, Box::new(Nil))))))))
+    );
+}
+
+#[test]
+fn reverse_nil() {
+    assert_eq!(Nil.reverse(), Nil);
+}
+
+// Implementing IntoIterator lets a for loop consume the list directly. The owning iterator holds whatever is left of the
+// list; each call to next moves the value out of the front Cons and replaces the remainder with its unboxed tail:
+struct IntoIter(List);
+
+impl Iterator for IntoIter {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<i32> {
+        match std::mem::replace(&mut self.0, Nil) {
+            Cons(value, next) => {
+                self.0 = *next;
+                Some(value)
+            }
+            Nil => None,
+        }
+    }
+}
+
+impl IntoIterator for List {
+    type Item = i32;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> IntoIter {
+        IntoIter(self)
+    }
+}
+
+// Going the other way, FromIterator lets collect build a List. A cons list has to be put together from its tail, so
+// from_iter gathers the values first and then conses them onto Nil back to front:
+use std::iter::FromIterator;
+
+impl FromIterator<i32> for List {
+    fn from_iter<I: IntoIterator<Item = i32>>(iter: I) -> List {
+        let values: Vec<i32> = iter.into_iter().collect();
+        values.into_iter().rev().fold(Nil, |list, value| Cons(value, Box::new(list)))
+    }
+}
+
+#[test]
+fn collect_a_consumed_list() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+    let mut values = Vec::new();
+
+    for value in list {
+        values.push(value);
+    }
+
+    assert_eq!(values, vec![1, 2, 3]);
+    assert_eq!(Nil.into_iter().collect::<Vec<i32>>(), Vec::<i32>::new());
+}
+
+#[test]
+fn collect_into_a_list() {
+    assert_eq!(vec![1, 2, 3].into_iter().collect::<List>(), Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil)))))));
+    assert_eq!(Vec::new().into_iter().collect::<List>(), Nil);
+}
+
+// prefix_sums builds a new list where each node holds the running total of every value up to and including it, so
+// [1, 2, 3] becomes [1, 3, 6]. The totals come out front to back, so we gather them in a Vec and collect that into
+// the new list:
+impl List {
+    fn prefix_sums(&self) -> List {
+        let mut sums = Vec::new();
+        let mut total = 0;
+        let mut current = self;
+
+        while let Cons(value, next) = current {
+            total += value;
+            sums.push(total);
+            current = next;
+        }
+
+        sums.into_iter().collect()
+    }
+}
+
+#[test]
+fn prefix_sums_of_a_list() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+
+    assert_eq!(list.prefix_sums(), Cons(1, Box::new(Cons(3, Box::new(Cons(6, Box::new(Nil)))))));
+    assert_eq!(Nil.prefix_sums(), Nil);
+}
+
+// take and skip mirror the iterator adapters of the same name: take builds a new list from the first n values and skip
+// builds one from everything after them. Both walk the list by reference and stop at Nil, so asking for more values than
+// the list holds just gives back the whole list from take and an empty one from skip:
+impl List {
+    fn take(&self, n: usize) -> List {
+        let mut values = Vec::new();
+        let mut current = self;
+
+        while let Cons(value, next) = current {
+            if values.len() == n {
+                break;
+            }
+            values.push(*value);
+            current = next;
+        }
+
+        values.into_iter().collect()
+    }
+
+    fn skip(&self, n: usize) -> List {
+        let mut values = Vec::new();
+        let mut current = self;
+        let mut skipped = 0;
+
+        while let Cons(value, next) = current {
+            if skipped < n {
+                skipped += 1;
+            } else {
+                values.push(*value);
+            }
+            current = next;
+        }
+
+        values.into_iter().collect()
+    }
+}
+
+#[test]
+fn take_from_a_list() {
+    let list: List = vec![1, 2, 3, 4, 5].into_iter().collect();
+
+    assert_eq!(list.take(2), Cons(1, Box::new(Cons(2, Box::new(Nil)))));
+    assert_eq!(list.take(0), Nil);
+    assert_eq!(list.take(10), list.take(5));
+    assert_eq!(list.take(10).into_iter().collect::<Vec<i32>>(), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn skip_into_a_list() {
+    let list: List = vec![1, 2, 3, 4, 5].into_iter().collect();
+
+    assert_eq!(list.skip(3), Cons(4, Box::new(Cons(5, Box::new(Nil)))));
+    assert_eq!(list.skip(0).into_iter().collect::<Vec<i32>>(), vec![1, 2, 3, 4, 5]);
+    assert_eq!(list.skip(5), Nil);
+    assert_eq!(list.skip(10), Nil);
+}
+
+// append joins two lists by putting other where self's Nil was. Walking down to that Nil and reattaching by hand would
+// mean holding a mutable reference into the middle of the list, so instead we move self's values out into a Vec and cons
+// them back onto the front of other from the back. Either way it's a loop rather than recursion, so a long list can't
+// overflow the stack:
+impl List {
+    fn append(self, other: List) -> List {
+        let mut values = Vec::new();
+        let mut current = self;
+
+        while let Cons(value, next) = current {
+            values.push(value);
+            current = *next;
+        }
+
+        values.into_iter().rev().fold(other, |list, value| Cons(value, Box::new(list)))
+    }
+}
+
+#[test]
+fn append_two_lists() {
+    let front = Cons(1, Box::new(Cons(2, Box::new(Nil))));
+    let back = Cons(3, Box::new(Cons(4, Box::new(Nil))));
+
+    assert_eq!(
+        front.append(back),
+        Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Cons(4, Box::new(Nil))))))))
+    );
+}
+
+#[test]
+fn append_onto_nil() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Nil))));
+
+    assert_eq!(Nil.append(Cons(1, Box::new(Cons(2, Box::new(Nil))))), list);
+    assert_eq!(list.append(Nil), Cons(1, Box::new(Cons(2, Box::new(Nil)))));
+    assert_eq!(Nil.append(Nil), Nil);
+}
+
+// nth and last read a value without taking the list apart. Both walk the nodes by reference and hand back a reference
+// into the list, so the list stays usable afterwards. nth counts from 0 and returns None once it runs into Nil, and last
+// returns None for a list that is just Nil:
+impl List {
+    fn nth(&self, n: usize) -> Option<&i32> {
+        let mut current = self;
+        let mut index = 0;
+
+        while let Cons(value, next) = current {
+            if index == n {
+                return Some(value);
+            }
+            index += 1;
+            current = next;
+        }
+
+        None
+    }
+
+    fn last(&self) -> Option<&i32> {
+        let mut last = None;
+        let mut current = self;
+
+        while let Cons(value, next) = current {
+            last = Some(value);
+            current = next;
+        }
+
+        last
+    }
+}
+
+#[test]
+fn nth_value_of_a_list() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+
+    assert_eq!(list.nth(0), Some(&1));
+    assert_eq!(list.nth(2), Some(&3));
+    assert_eq!(list.nth(3), None);
+    assert_eq!(Nil.nth(0), None);
+}
+
+#[test]
+fn last_value_of_a_list() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+
+    assert_eq!(list.last(), Some(&3));
+    assert_eq!(Cons(7, Box::new(Nil)).last(), Some(&7));
+    assert_eq!(Nil.last(), None);
+    assert_eq!(list.nth(0), Some(&1));
+}
+
+// split_at works like take and skip at once, but it consumes the list so nothing has to be copied. It moves the first n
+// values out into a Vec and collects them into the front list; whatever node it stopped at is
+// already the rest of the list, tail and all, so it's handed back as it is. An n past the end just puts every value in
+// the front list and leaves Nil for the rest:
+impl List {
+    fn split_at(self, n: usize) -> (List, List) {
+        let mut values = Vec::new();
+        let mut current = self;
+
+        while values.len() < n {
+            match current {
+                Cons(value, next) => {
+                    values.push(value);
+                    current = *next;
+                }
+                Nil => break,
+            }
+        }
+
+        let front = values.into_iter().collect();
+        (front, current)
+    }
+}
+
+#[test]
+fn split_a_list() {
+    let list = || vec![1, 2, 3, 4].into_iter().collect::<List>();
+
+    assert_eq!(
+        list().split_at(2),
+        (Cons(1, Box::new(Cons(2, Box::new(Nil)))), Cons(3, Box::new(Cons(4, Box::new(Nil)))))
+    );
+    assert_eq!(list().split_at(0), (Nil, list()));
+    assert_eq!(list().split_at(4), (list(), Nil));
+    assert_eq!(list().split_at(10), (list(), Nil));
+    assert_eq!(Nil.split_at(1), (Nil, Nil));
+}
+
+// insert puts a value at index by splitting the list there and consing the value onto the front of the rest before
+// appending it back, so the values after it each move down one place. Index 0 puts it at the front and an index equal
+// to the length puts it at the end. Anything past that has nowhere to go, and since the list was moved in, insert hands
+// it back unchanged in the Err rather than dropping it:
+impl List {
+    fn insert(self, index: usize, value: i32) -> Result<List, List> {
+        let mut length = 0;
+        let mut current = &self;
+        while let Cons(_, next) = current {
+            length += 1;
+            current = next;
+        }
+
+        if index > length {
+            return Err(self);
+        }
+
+        let (front, rest) = self.split_at(index);
+        Ok(front.append(Cons(value, Box::new(rest))))
+    }
+}
+
+#[test]
+fn insert_into_a_list() {
+    let list = || vec![1, 2, 3].into_iter().collect::<List>();
+    let values = |list: List| list.into_iter().collect::<Vec<i32>>();
+
+    assert_eq!(values(list().insert(0, 9).unwrap()), vec![9, 1, 2, 3]);
+    assert_eq!(values(list().insert(2, 9).unwrap()), vec![1, 2, 9, 3]);
+    assert_eq!(values(list().insert(3, 9).unwrap()), vec![1, 2, 3, 9]);
+    assert_eq!(Nil.insert(0, 9), Ok(Cons(9, Box::new(Nil))));
+}
+
+#[test]
+fn insert_past_the_end_gives_the_list_back() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Nil))));
+
+    assert_eq!(list.insert(3, 9), Err(Cons(1, Box::new(Cons(2, Box::new(Nil))))));
+    assert_eq!(Nil.insert(1, 9), Err(Nil));
+}
+
+// The List we've been using already derives PartialEq, so == tells us whether two whole lists hold the same values in
+// the same order. contains_sublist asks a harder question: whether needle's values show up one after another somewhere
+// inside self. starts_with walks both lists together and succeeds once it runs out of needle, and contains_sublist tries
+// it from every node in turn, including the Nil at the end, so an empty needle is found in any list:
+impl List {
+    fn starts_with(&self, prefix: &List) -> bool {
+        let mut list = self;
+        let mut prefix = prefix;
+
+        loop {
+            match (list, prefix) {
+                (_, Nil) => return true,
+                (Cons(a, next), Cons(b, rest)) if a == b => {
+                    list = next;
+                    prefix = rest;
+                }
+                _ => return false,
+            }
+        }
+    }
+
+    fn contains_sublist(&self, needle: &List) -> bool {
+        let mut current = self;
+
+        loop {
+            if current.starts_with(needle) {
+                return true;
+            }
+            match current {
+                Cons(_, next) => current = next,
+                Nil => return false,
+            }
+        }
+    }
+}
+
+#[test]
+fn find_a_sublist() {
+    let list: List = vec![1, 2, 1, 2, 3, 4].into_iter().collect();
+
+    assert!(list.contains_sublist(&Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))))));
+    assert!(list.contains_sublist(&Cons(3, Box::new(Cons(4, Box::new(Nil))))));
+    assert!(list.contains_sublist(&list.take(6)));
+    assert!(list == list.take(6));
+}
+
+#[test]
+fn sublist_that_is_not_there() {
+    let list: List = vec![1, 2, 1, 2, 3, 4].into_iter().collect();
+
+    assert!(!list.contains_sublist(&Cons(2, Box::new(Cons(4, Box::new(Nil))))));
+    assert!(!list.contains_sublist(&Cons(4, Box::new(Cons(5, Box::new(Nil))))));
+    assert!(!Nil.contains_sublist(&Cons(1, Box::new(Nil))));
+}
+
+#[test]
+fn empty_sublist_is_always_there() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Nil))));
+
+    assert!(list.contains_sublist(&Nil));
+    assert!(Nil.contains_sublist(&Nil));
+}
+
+// The list we've been using can only hold i32 values. Just like the generic types in Chapter 10, we can make the
+// element type a parameter so the same recursive structure works for a list of anything:
+#[derive(Debug, PartialEq)]
+enum List<T> {
+    Cons(T, Box<List<T>>),
+    Nil,
+}
+
+use crate::List::{Cons, Nil};
+
+// The generic list gets the same FromIterator impl as the i32 one, so any iterator can be collected into a List<T>:
+use std::iter::FromIterator;
+
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> List<T> {
+        let values: Vec<T> = iter.into_iter().collect();
+        values.into_iter().rev().fold(Nil, |list, value| Cons(value, Box::new(list)))
+    }
+}
+
+// With a generic list we can write map, which mirrors Iterator::map: it applies f to every value and builds a new
+// List<U> in the same order. We walk the nodes with a loop, gathering the mapped values, and then collect them into
+// the new list.
+impl<T> List<T> {
+    fn map<U, F: Fn(&T) -> U>(&self, f: F) -> List<U> {
+        let mut values = Vec::new();
+        let mut current = self;
+
+        while let Cons(value, next) = current {
+            values.push(f(value));
+            current = next;
+        }
+
+        values.into_iter().collect()
+    }
+}
+
+#[test]
+fn map_a_list() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+
+    assert_eq!(
+        list.map(|value| value.to_string()),
+        Cons(String::from("1"), Box::new(Cons(String::from("2"), Box::new(Cons(String::from("3"), Box::new(Nil))))))
+    );
+}
+
+// Since the element type can be anything, it can be another list. flatten takes a list of lists of i32 and joins the
+// inner lists together in order. It moves every value out of the nested lists into a Vec as it walks them, then collects
+// the flat list from it like map does:
+fn flatten(list: List<List<i32>>) -> List<i32> {
+    let mut values = Vec::new();
+    let mut outer = list;
+
+    while let Cons(mut inner, rest) = outer {
+        while let Cons(value, next) = inner {
+            values.push(value);
+            inner = *next;
+        }
+
+        outer = *rest;
+    }
+
+    values.into_iter().collect()
+}
+
+#[test]
+fn flatten_a_list_of_lists() {
+    let lists = Cons(
+        Cons(1, Box::new(Cons(2, Box::new(Nil)))),
+        Box::new(Cons(
+            Cons(3, Box::new(Nil)),
+            Box::new(Cons(Cons(4, Box::new(Cons(5, Box::new(Nil)))), Box::new(Nil))),
+        )),
+    );
+
+    assert_eq!(
+        flatten(lists),
+        Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Cons(4, Box::new(Cons(5, Box::new(Nil))))))))))
+    );
+}
+
+// fold is the most general of these: it threads an accumulator through every value from front to back, so sums,
+// products, and joins are all just a different init and f. Unlike map it doesn't need to rebuild a list, so it can
+// update the accumulator as it walks the nodes:
+impl<T> List<T> {
+    fn fold<B, F: Fn(B, &T) -> B>(&self, init: B, f: F) -> B {
+        let mut accumulator = init;
+        let mut current = self;
+
+        while let Cons(value, next) = current {
+            accumulator = f(accumulator, value);
+            current = next;
+        }
+
+        accumulator
+    }
+}
+
+#[test]
+fn fold_a_list_into_a_sum() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+
+    assert_eq!(list.fold(0, |sum, value| sum + value), 6);
+    assert_eq!(list.fold(1, |product, value| product * value), 6);
+    assert_eq!(Nil.fold(0, |sum: i32, value: &i32| sum + value), 0);
+}
+
+#[test]
+fn fold_a_list_into_a_reversed_string() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+
+    assert_eq!(list.fold(String::new(), |reversed, value| format!("{}{}", value, reversed)), "321");
+}
+
+// Searching only needs to compare values, so contains and position are on List<T> where T: PartialEq. position walks
+// the nodes counting from 0 and stops at the first value equal to target, the same as Iterator::position, and contains
+// is just whether position found anything:
+impl<T: PartialEq> List<T> {
+    fn contains(&self, target: &T) -> bool {
+        self.position(target).is_some()
+    }
+
+    fn position(&self, target: &T) -> Option<usize> {
+        let mut current = self;
+        let mut index = 0;
+
+        while let Cons(value, next) = current {
+            if value == target {
+                return Some(index);
+            }
+            index += 1;
+            current = next;
+        }
+
+        None
+    }
+}
+
+#[test]
+fn search_a_list_of_strings() {
+    let list: List<String> = vec!["red", "green", "blue", "green"].into_iter().map(String::from).collect();
+
+    assert!(list.contains(&String::from("blue")));
+    assert_eq!(list.position(&String::from("red")), Some(0));
+    assert_eq!(list.position(&String::from("green")), Some(1));
+    assert!(!list.contains(&String::from("purple")));
+    assert_eq!(list.position(&String::from("purple")), None);
+    assert!(!Nil.contains(&String::from("red")));
+}
+
+// zip pairs up the values of two lists node by node, like Iterator::zip: the first value of each list, then the second,
+// and so on until either list runs into Nil, so the result is as long as the shorter one. The new list owns its pairs,
+// so the values are cloned out of both lists, which are only borrowed:
+impl<T: Clone> List<T> {
+    fn zip<U: Clone>(&self, other: &List<U>) -> List<(T, U)> {
+        let mut pairs = Vec::new();
+        let mut left = self;
+        let mut right = other;
+
+        while let (Cons(a, next_left), Cons(b, next_right)) = (left, right) {
+            pairs.push((a.clone(), b.clone()));
+            left = next_left;
+            right = next_right;
+        }
+
+        pairs.into_iter().collect()
+    }
+}
+
+#[test]
+fn zip_lists_of_different_lengths() {
+    let numbers = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+    let words = Cons(String::from("one"), Box::new(Cons(String::from("two"), Box::new(Nil))));
+
+    assert_eq!(
+        numbers.zip(&words),
+        Cons((1, String::from("one")), Box::new(Cons((2, String::from("two")), Box::new(Nil))))
+    );
+    assert_eq!(
+        words.zip(&numbers),
+        Cons((String::from("one"), 1), Box::new(Cons((String::from("two"), 2), Box::new(Nil))))
+    );
+    assert_eq!(numbers.zip(&Nil::<char>), Nil);
+}
+
+// flat_map is map followed by the kind of join flatten does: f turns each value into a whole list, and the lists come
+// out one after another in the order of the values that made them. Each sublist's values are moved into one Vec as
+// they're produced, and the result is collected from that Vec like every other list here:
+impl<T> List<T> {
+    fn flat_map<U, F: Fn(&T) -> List<U>>(&self, f: F) -> List<U> {
+        let mut values = Vec::new();
+        let mut current = self;
+
+        while let Cons(value, next) = current {
+            let mut inner = f(value);
+            while let Cons(value, next) = inner {
+                values.push(value);
+                inner = *next;
+            }
+            current = next;
+        }
+
+        values.into_iter().collect()
+    }
+}
+
+#[test]
+fn flat_map_each_value_into_two() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+    let pair = |value: &i32| Cons(*value, Box::new(Cons(value * 10, Box::new(Nil))));
+
+    assert_eq!(
+        list.flat_map(pair),
+        Cons(1, Box::new(Cons(10, Box::new(Cons(2, Box::new(Cons(20, Box::new(Cons(3, Box::new(Cons(30, Box::new(Nil))))))))))))
+    );
+    assert_eq!(Nil.flat_map(pair), Nil);
+}
+
+#[test]
+fn flat_map_can_drop_values() {
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+    let evens_twice = |value: &i32| {
+        if value % 2 == 0 {
+            Cons(value.to_string(), Box::new(Cons(value.to_string(), Box::new(Nil))))
+        } else {
+            Nil
+        }
+    };
+
+    assert_eq!(
+        list.flat_map(evens_twice),
+        Cons(String::from("2"), Box::new(Cons(String::from("2"), Box::new(Nil))))
+    );
+}
+
+// chunks groups the values into lists of size values each, front to back, like slice::chunks. The last chunk gets
+// whatever is left over, so it can be shorter. Each chunk is collected into a Vec of cloned values and built into a list
+// once it's full, and the list of chunks is then collected the same way. A size of 0 would never fill a chunk,
+// so, also like slice::chunks, it panics:
+impl<T: Clone> List<T> {
+    fn chunks(&self, size: usize) -> List<List<T>> {
+        assert!(size != 0, "chunk size must be greater than 0");
+
+        let build = |values: Vec<T>| values.into_iter().collect::<List<T>>();
+        let mut chunks = Vec::new();
+        let mut chunk = Vec::new();
+        let mut current = self;
+
+        while let Cons(value, next) = current {
+            chunk.push(value.clone());
+            if chunk.len() == size {
+                chunks.push(build(std::mem::take(&mut chunk)));
+            }
+            current = next;
+        }
+        if !chunk.is_empty() {
+            chunks.push(build(chunk));
+        }
+
+        chunks.into_iter().collect()
+    }
+}
+
+#[test]
+fn chunk_a_list_by_two() {
+    let list: List<_> = vec![1, 2, 3, 4, 5].into_iter().collect();
+
+    assert_eq!(
+        list.chunks(2),
+        Cons(
+            Cons(1, Box::new(Cons(2, Box::new(Nil)))),
+            Box::new(Cons(
+                Cons(3, Box::new(Cons(4, Box::new(Nil)))),
+                Box::new(Cons(Cons(5, Box::new(Nil)), Box::new(Nil))),
+            )),
+        )
+    );
+    assert_eq!(list.chunks(5), Cons(list.map(|value| *value), Box::new(Nil)));
+    assert_eq!(Nil::<i32>.chunks(2), Nil);
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be greater than 0")]
+fn chunks_of_size_zero() {
+    Cons(1, Box::new(Nil)).chunks(0);
+}
+
 // Treating Smart Pointers Like Regular References with the Deref Trait
 // Implementing the Deref trait allows you to customize the behavior of the dereference operator, * (as opposed
 // to the multiplication or glob operator). By implementing Deref in such a way that a smart pointer can be
@@ -175,6 +870,55 @@ impl<T> Deref for MyBox<T> {
 // instead of a reference to the value, the value would be moved out of self. We don’t want to take ownership of the
 // inner value inside MyBox<T> in this case or in most cases where we use the dereference operator.
 
+// Deref makes MyBox<T> look like a pointer, but it still stores its value inline, so a recursive
+// enum List { Cons(i32, MyBox<List>), Nil } has the same infinite size problem as the very first cons list. To stand
+// in for Box<T> a type has to actually put its value on the heap. HeapBox<T> does that by holding a Box<T> inside,
+// and with the same Deref implementation as MyBox it can back the cons list:
+struct HeapBox<T>(Box<T>);
+
+impl<T> HeapBox<T> {
+    fn new(x: T) -> HeapBox<T> {
+        HeapBox(Box::new(x))
+    }
+}
+
+impl<T> Deref for HeapBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+enum List {
+    Cons(i32, HeapBox<List>),
+    Nil,
+}
+
+use crate::List::{Cons, Nil};
+
+impl List {
+    fn sum(&self) -> i32 {
+        let mut total = 0;
+        let mut current = self;
+
+        // Deref coercion turns each &HeapBox<List> into the &List it points to
+        while let Cons(value, next) = current {
+            total += value;
+            current = next;
+        }
+
+        total
+    }
+}
+
+#[test]
+fn sum_a_heap_box_list() {
+    let list = Cons(1, HeapBox::new(Cons(2, HeapBox::new(Cons(3, HeapBox::new(Nil))))));
+
+    assert_eq!(list.sum(), 6);
+}
+
 // Implicit Deref Coercions with Functions and Methods
 // Deref coercion is a convenience that Rust performs on arguments to functions and methods. Deref coercion converts a
 // reference to a type that implements Deref into a reference to a type that Deref can convert the original type into.
@@ -204,6 +948,25 @@ fn main() {
     hello(&(*m)[..]);
 }
 
+// hello only prints, so we can't check the coercion in a test. greeting_of takes the same &str parameter but returns the
+// greeting instead, which lets us assert that a &MyBox<String> really does reach it through the Deref chain
+// (&MyBox<String> to &String to &str), just as a plain &String does through String's own Deref:
+fn greeting_of(name: &str) -> String {
+    format!("Hello {}", name)
+}
+
+#[test]
+fn greeting_of_a_my_box() {
+    assert_eq!(greeting_of(&MyBox::new(String::from("Rust"))), "Hello Rust");
+}
+
+#[test]
+fn greeting_of_a_string() {
+    let name = String::from("Rust");
+
+    assert_eq!(greeting_of(&name), "Hello Rust");
+}
+
 // Similar to how you use the Deref trait to override the * operator on immutable references, you can use the DerefMut trait
 // to override the * operator on mutable references.
 
@@ -270,6 +1033,193 @@ fn main() {
     println!("CustomSmartPointer dropped before the end of main.");
 }
 
+// CustomSmartPointer only prints when it's dropped, which we can watch but can't assert on. To make drop order testable
+// we can have each value record its name in a shared log instead. DropLog wraps an Rc<RefCell<Vec<String>>> (both covered
+// later in this chapter) so every clone appends to the same Vec, and Tracked pushes its name onto the log in drop:
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Clone, Default)]
+struct DropLog(Rc<RefCell<Vec<String>>>);
+
+impl DropLog {
+    fn new() -> DropLog {
+        DropLog::default()
+    }
+
+    fn track(&self, name: &str) -> Tracked {
+        Tracked {
+            name: String::from(name),
+            log: self.clone(),
+        }
+    }
+
+    fn entries(&self) -> Vec<String> {
+        self.0.borrow().clone()
+    }
+}
+
+struct Tracked {
+    name: String,
+    log: DropLog,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.log.0.borrow_mut().push(self.name.clone());
+    }
+}
+
+#[test]
+fn tracked_values_drop_in_reverse_order() {
+    let log = DropLog::new();
+
+    {
+        let _a = log.track("a");
+        let b = log.track("b");
+        let _c = log.track("c");
+        drop(b);
+        assert_eq!(log.entries(), vec!["b"]);
+    }
+
+    assert_eq!(log.entries(), vec!["b", "c", "a"]);
+}
+
+// Drop doesn't have to be about freeing memory. ScopeGuard holds any closure and calls it from drop, so whatever cleanup the
+// closure does runs when the guard goes out of scope, however the scope is left: at the closing curly bracket, through an
+// early return, or with the ? operator. Rust calls drop exactly once per value, so the closure can't run twice:
+struct ScopeGuard<F: FnMut()> {
+    f: F,
+}
+
+impl<F: FnMut()> Drop for ScopeGuard<F> {
+    fn drop(&mut self) {
+        (self.f)();
+    }
+}
+
+fn guard<F: FnMut()>(f: F) -> ScopeGuard<F> {
+    ScopeGuard { f }
+}
+
+#[test]
+fn guard_runs_once_when_leaving_scope() {
+    use std::cell::Cell;
+
+    let runs = Cell::new(0);
+    {
+        let _guard = guard(|| runs.set(runs.get() + 1));
+        assert_eq!(runs.get(), 0);
+    }
+    assert_eq!(runs.get(), 1);
+
+    let add_parsed = |a: &str, b: &str| -> Option<i32> {
+        let _guard = guard(|| runs.set(runs.get() + 1));
+        let a: i32 = a.parse().ok()?;
+        let b: i32 = b.parse().ok()?;
+        Some(a + b)
+    };
+
+    assert_eq!(add_parsed("2", "3"), Some(5));
+    assert_eq!(runs.get(), 2);
+    assert_eq!(add_parsed("two", "3"), None);
+    assert_eq!(runs.get(), 3);
+}
+
+// Deref and Drop together make a wrapper that's almost invisible. Logged<T> derefs to the value it holds, so a Logged<Vec<i32>>
+// can be pushed to, measured, and iterated like the Vec itself, and DerefMut lets the calls that need &mut go through too.
+// The only difference shows up at the end: dropping it writes "{name} dropped" to a DropLog:
+use std::ops::{Deref, DerefMut};
+
+struct Logged<T> {
+    inner: T,
+    name: String,
+    log: DropLog,
+}
+
+impl<T> Logged<T> {
+    fn new(inner: T, name: &str, log: &DropLog) -> Logged<T> {
+        Logged {
+            inner,
+            name: String::from(name),
+            log: log.clone(),
+        }
+    }
+}
+
+impl<T> Deref for Logged<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for Logged<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<T> Drop for Logged<T> {
+    fn drop(&mut self) {
+        self.log.0.borrow_mut().push(format!("{} dropped", self.name));
+    }
+}
+
+#[test]
+fn logged_vec_behaves_like_a_vec_and_logs_its_drop() {
+    let log = DropLog::new();
+
+    {
+        let mut numbers = Logged::new(vec![1, 2], "numbers", &log);
+        numbers.push(3);
+
+        assert_eq!(numbers.len(), 3);
+        assert_eq!(numbers.iter().sum::<i32>(), 6);
+        assert_eq!(*numbers, vec![1, 2, 3]);
+        assert!(log.entries().is_empty());
+    }
+
+    assert_eq!(log.entries(), vec!["numbers dropped"]);
+}
+
+// drop_order_demo puts the chapter's drop examples in one scope and hands back the order they ran in. A CustomSmartPointer
+// still only prints, so each one is paired with a Tracked in a tuple: a tuple drops its fields first to last, so the
+// Tracked records its name right after the CustomSmartPointer has printed. MyBox has no Drop of its own, but dropping it
+// drops the value inside, which here is a Tracked too. The values dropped early with std::mem::drop come first, in the order
+// drop was called, then the rest go in the reverse of the order they were created:
+fn drop_order_demo() -> Vec<String> {
+    let log = DropLog::new();
+
+    {
+        let _first = (CustomSmartPointer { data: String::from("my stuff") }, log.track("custom smart pointer"));
+        let boxed = MyBox::new(log.track("my box"));
+        let _tracked = log.track("tracked");
+        let early = (CustomSmartPointer { data: String::from("some data") }, log.track("early custom smart pointer"));
+        let _last = MyBox::new(log.track("last my box"));
+
+        std::mem::drop(early);
+        std::mem::drop(boxed);
+    }
+
+    log.entries()
+}
+
+#[test]
+fn drop_order_across_smart_pointers() {
+    assert_eq!(
+        drop_order_demo(),
+        vec![
+            "early custom smart pointer",
+            "my box",
+            "last my box",
+            "tracked",
+            "custom smart pointer",
+        ]
+    );
+}
+
 // Rc<T>, the Reference Counted Smart Pointer
 // In the majority of cases, ownership is clear: you know exactly which variable owns a given value. However, there are cases
 // when a single value might have multiple owners. For example, in graph data structures, multiple edges might point to the
@@ -425,21 +1375,64 @@ pub struct LimitTracker<'a, T: Messenger> {
     messenger: &'a T,
     value: usize,
     max: usize,
+    // Only Some when the tracker was created with history turned on
+    history: Option<Vec<usize>>,
 }
 
 impl<'a, T> LimitTracker<'a, T>
     where T: Messenger
 {
     pub fn new(messenger: &T, max: usize) -> LimitTracker<T> {
+        LimitTracker::with_history(messenger, max, false)
+    }
+
+    pub fn with_history(messenger: &T, max: usize, record_history: bool) -> LimitTracker<T> {
         LimitTracker {
             messenger,
             value: 0,
             max,
+            history: if record_history { Some(Vec::new()) } else { None },
+        }
+    }
+
+    pub fn history(&self) -> &[usize] {
+        self.history.as_deref().unwrap_or(&[])
+    }
+
+    pub fn total_usage(&self) -> usize {
+        let mut total = 0;
+
+        for value in self.history() {
+            total += value;
+        }
+
+        total
+    }
+
+    pub fn peak_usage(&self) -> Option<usize> {
+        let mut peak = None;
+
+        for &value in self.history() {
+            match peak {
+                Some(max) if max >= value => {}
+                _ => peak = Some(value),
+            }
         }
+
+        peak
+    }
+
+    // Starting over doesn't send anything and isn't a set, so the history is left as it was
+    pub fn reset(&mut self) {
+        self.value = 0;
     }
 
     pub fn set_value(&mut self, value: usize) {
         self.value = value;
+
+        if let Some(history) = self.history.as_mut() {
+            history.push(value);
+        }
         
         let percentage_of_max = self.value as f64 / self.max as f64;
 
@@ -474,14 +1467,380 @@ mod tests {
         }
     }
 
+    // borrow_mut panics if the RefCell is already borrowed (see Listing 15-23 below). try_borrow_mut makes the same check
+    // but hands back an error instead, so try_record can report the conflict and let the caller decide what to do
+    impl MockMessenger {
+        fn try_record(&self, msg: &str) -> Result<(), String> {
+            let mut sent_messages = self
+                .sent_messages
+                .try_borrow_mut()
+                .map_err(|_| format!("couldn't record {:?}: the messages are already borrowed", msg))?;
+
+            sent_messages.push(String::from(msg));
+            Ok(())
+        }
+    }
+
     #[test]
     fn it_sends_an_over_75_percent_warning_message() {
-        // --snip--
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
 
+        limit_tracker.set_value(80);
+
+        assert_eq!(mock_messenger.sent_messages.borrow().len(), 1);
+    }
+
+    #[test]
+    fn it_records_history_and_resets_quietly() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::with_history(&mock_messenger, 100, true);
+
+        limit_tracker.set_value(10);
+        limit_tracker.set_value(50);
+        limit_tracker.set_value(80);
+        assert_eq!(limit_tracker.history(), &[10, 50, 80]);
+        assert_eq!(mock_messenger.sent_messages.borrow().len(), 1);
+
+        limit_tracker.reset();
+        assert_eq!(limit_tracker.value, 0);
         assert_eq!(mock_messenger.sent_messages.borrow().len(), 1);
+        assert_eq!(limit_tracker.history(), &[10, 50, 80]);
+
+        let mut untracked = LimitTracker::new(&mock_messenger, 100);
+        untracked.set_value(10);
+        assert!(untracked.history().is_empty());
+    }
+
+    #[test]
+    fn it_totals_and_peaks_recorded_usage() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::with_history(&mock_messenger, 100, true);
+        assert_eq!(limit_tracker.total_usage(), 0);
+        assert_eq!(limit_tracker.peak_usage(), None);
+
+        for value in &[20, 60, 40] {
+            limit_tracker.set_value(*value);
+        }
+
+        assert_eq!(limit_tracker.total_usage(), 120);
+        assert_eq!(limit_tracker.peak_usage(), Some(60));
+    }
+
+    #[test]
+    fn it_broadcasts_to_every_messenger() {
+        let first = MockMessenger::new();
+        let second = MockMessenger::new();
+        let messenger = MultiMessenger::new(vec![&first, &second]);
+        let mut limit_tracker = LimitTracker::new(&messenger, 100);
+
+        limit_tracker.set_value(80);
+
+        let expected = vec![String::from("Warning: You've used up over 75% of your quota!")];
+        assert_eq!(*first.sent_messages.borrow(), expected);
+        assert_eq!(*second.sent_messages.borrow(), expected);
+    }
+
+    #[test]
+    fn it_only_passes_messages_at_or_above_the_threshold() {
+        let mock_messenger = MockMessenger::new();
+        let messenger = ThresholdMessenger::new(&mock_messenger, Level::Error);
+        let mut limit_tracker = LimitTracker::new(&messenger, 100);
+
+        limit_tracker.set_value(80);
+        limit_tracker.set_value(95);
+        assert!(mock_messenger.sent_messages.borrow().is_empty());
+
+        limit_tracker.set_value(120);
+        messenger.send("Just checking in");
+        assert_eq!(
+            *mock_messenger.sent_messages.borrow(),
+            vec![String::from("Error: You are over your quota!")]
+        );
+        assert_eq!(Level::of("Urgent warning: You've used up over 90% of your quota!"), Level::Urgent);
+        assert!(Level::Warning < Level::Urgent);
+    }
+
+    #[test]
+    fn it_reports_a_borrow_conflict_instead_of_panicking() {
+        let mock_messenger = MockMessenger::new();
+        assert_eq!(mock_messenger.try_record("first"), Ok(()));
+
+        {
+            let _reading = mock_messenger.sent_messages.borrow();
+            assert_eq!(
+                mock_messenger.try_record("second"),
+                Err(String::from("couldn't record \"second\": the messages are already borrowed"))
+            );
+        }
+
+        assert_eq!(mock_messenger.try_record("third"), Ok(()));
+        assert_eq!(*mock_messenger.sent_messages.borrow(), vec![String::from("first"), String::from("third")]);
     }
 }
 
+// The mock isn't the only Messenger that needs interior mutability. Writing to a file or any other io::Write takes &mut,
+// but send only gets &self, so WriterMessenger keeps its writer in a RefCell and borrows it mutably for each message.
+// Handing it a Vec<u8> keeps the messages in memory, which is handy for checking exactly what LimitTracker sent:
+use std::io::Write;
+
+pub struct WriterMessenger<W: Write> {
+    out: RefCell<W>,
+}
+
+impl<W: Write> WriterMessenger<W> {
+    pub fn new(out: W) -> WriterMessenger<W> {
+        WriterMessenger { out: RefCell::new(out) }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out.into_inner()
+    }
+}
+
+impl<W: Write> Messenger for WriterMessenger<W> {
+    fn send(&self, msg: &str) {
+        writeln!(self.out.borrow_mut(), "{}", msg).expect("failed to write message");
+    }
+}
+
+#[test]
+fn writer_messenger_writes_each_message_on_a_line() {
+    let messenger = WriterMessenger::new(Vec::new());
+    let mut limit_tracker = LimitTracker::new(&messenger, 100);
+
+    limit_tracker.set_value(80);
+    limit_tracker.set_value(100);
+
+    assert_eq!(
+        messenger.into_inner(),
+        b"Warning: You've used up over 75% of your quota!\nError: You are over your quota!\n".to_vec()
+    );
+}
+
+// Because LimitTracker only needs something that implements Messenger, a messenger can also be built out of other
+// messengers. MultiMessenger holds trait object references and forwards every message to each of them, so one tracker
+// can log to a WriterMessenger and notify a mock at the same time:
+pub struct MultiMessenger<'a> {
+    messengers: Vec<&'a dyn Messenger>,
+}
+
+impl<'a> MultiMessenger<'a> {
+    pub fn new(messengers: Vec<&'a dyn Messenger>) -> MultiMessenger<'a> {
+        MultiMessenger { messengers }
+    }
+}
+
+impl<'a> Messenger for MultiMessenger<'a> {
+    fn send(&self, msg: &str) {
+        for messenger in &self.messengers {
+            messenger.send(msg);
+        }
+    }
+}
+
+// Messengers can filter as well as forward. Every message LimitTracker sends starts by saying how serious it is, so Level
+// reads that back from the start of the message, and anything without one of those prefixes counts as Info. The
+// variants are listed from least to most serious, so the derived Ord compares them in that order. ThresholdMessenger
+// forwards a message to the messenger it wraps only when its level is at least min_level and silently drops the rest,
+// which lets a tracker send only the most important messages to something like a pager:
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Info,
+    Warning,
+    Urgent,
+    Error,
+}
+
+impl Level {
+    pub fn of(msg: &str) -> Level {
+        if msg.starts_with("Error:") {
+            Level::Error
+        } else if msg.starts_with("Urgent warning:") {
+            Level::Urgent
+        } else if msg.starts_with("Warning:") {
+            Level::Warning
+        } else {
+            Level::Info
+        }
+    }
+}
+
+pub struct ThresholdMessenger<'a, M: Messenger> {
+    inner: &'a M,
+    min_level: Level,
+}
+
+impl<'a, M: Messenger> ThresholdMessenger<'a, M> {
+    pub fn new(inner: &'a M, min_level: Level) -> ThresholdMessenger<'a, M> {
+        ThresholdMessenger { inner, min_level }
+    }
+}
+
+impl<'a, M: Messenger> Messenger for ThresholdMessenger<'a, M> {
+    fn send(&self, msg: &str) {
+        if Level::of(msg) >= self.min_level {
+            self.inner.send(msg);
+        }
+    }
+}
+
+// Sometimes a test only cares how many messages of each kind went out, not their exact wording. CountingMessenger reads
+// each message's Level the same way and keeps a tally per level in a RefCell<HashMap>, so send can update it through
+// &self. Level derives Hash so it can be the key, and count reports 0 for a level that was never sent:
+use std::collections::HashMap;
+
+pub struct CountingMessenger {
+    counts: RefCell<HashMap<Level, usize>>,
+}
+
+impl CountingMessenger {
+    pub fn new() -> CountingMessenger {
+        CountingMessenger { counts: RefCell::new(HashMap::new()) }
+    }
+
+    pub fn count(&self, level: Level) -> usize {
+        self.counts.borrow().get(&level).copied().unwrap_or(0)
+    }
+}
+
+impl Messenger for CountingMessenger {
+    fn send(&self, msg: &str) {
+        *self.counts.borrow_mut().entry(Level::of(msg)).or_insert(0) += 1;
+    }
+}
+
+#[test]
+fn counting_messenger_tallies_each_level() {
+    let messenger = CountingMessenger::new();
+    let mut limit_tracker = LimitTracker::new(&messenger, 100);
+
+    for value in &[10, 76, 80, 91, 99, 100, 150, 50] {
+        limit_tracker.set_value(*value);
+    }
+    messenger.send("Just checking in");
+
+    assert_eq!(messenger.count(Level::Warning), 2);
+    assert_eq!(messenger.count(Level::Urgent), 2);
+    assert_eq!(messenger.count(Level::Error), 2);
+    assert_eq!(messenger.count(Level::Info), 1);
+}
+
+// The quota doesn't have to be written into the code. limit_tracker_from_env reads it from the QUOTA_MAX environment
+// variable and fails with a message if it's missing, isn't a whole number, or is 0, since percentages of a 0 quota make
+// no sense. It asks lookup for the variable rather than reading the environment itself, so a real caller passes
+// |key| std::env::var(key).ok() and a test can hand it a fixed value:
+pub fn limit_tracker_from_env<'a, M, F>(messenger: &'a M, lookup: F) -> Result<LimitTracker<'a, M>, String>
+where
+    M: Messenger,
+    F: Fn(&str) -> Option<String>,
+{
+    let value = lookup("QUOTA_MAX").ok_or_else(|| String::from("QUOTA_MAX is not set"))?;
+    let max: usize = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("QUOTA_MAX must be a whole number, got {:?}", value))?;
+
+    if max == 0 {
+        return Err(String::from("QUOTA_MAX must be greater than 0"));
+    }
+
+    Ok(LimitTracker::new(messenger, max))
+}
+
+#[test]
+fn limit_tracker_with_quota_max_set() {
+    let messenger = CountingMessenger::new();
+    let mut limit_tracker = limit_tracker_from_env(&messenger, |key| match key {
+        "QUOTA_MAX" => Some(String::from("200")),
+        _ => None,
+    })
+    .unwrap();
+
+    assert_eq!(limit_tracker.max, 200);
+    limit_tracker.set_value(160);
+    assert_eq!(messenger.count(Level::Warning), 1);
+}
+
+#[test]
+fn limit_tracker_with_quota_max_unset_or_invalid() {
+    let messenger = CountingMessenger::new();
+    let with = |value: &'static str| move |_: &str| Some(String::from(value));
+
+    assert_eq!(limit_tracker_from_env(&messenger, |_| None).err(), Some(String::from("QUOTA_MAX is not set")));
+    assert_eq!(
+        limit_tracker_from_env(&messenger, with("lots")).err(),
+        Some(String::from("QUOTA_MAX must be a whole number, got \"lots\""))
+    );
+    assert_eq!(
+        limit_tracker_from_env(&messenger, with("0")).err(),
+        Some(String::from("QUOTA_MAX must be greater than 0"))
+    );
+}
+
+// A messenger that's slow to deliver, like one sending email, would hold up LimitTracker on every set_value. With the
+// threads and channels from Chapter 16, AsyncMessenger hands the work to a background thread instead: send only puts the
+// message on a channel, and the thread takes messages off in the order they were sent and passes each one to the
+// messenger it owns. That messenger is moved to the thread, so it has to be Send. shutdown drops the sending end, which
+// ends the thread's loop once it has delivered everything already queued, then joins the thread and gives the inner
+// messenger back:
+use std::sync::mpsc;
+use std::thread;
+
+pub struct AsyncMessenger<M: Messenger + Send + 'static> {
+    sender: mpsc::Sender<String>,
+    worker: thread::JoinHandle<M>,
+}
+
+impl<M: Messenger + Send + 'static> AsyncMessenger<M> {
+    pub fn new(inner: M) -> AsyncMessenger<M> {
+        let (sender, receiver) = mpsc::channel::<String>();
+        let worker = thread::spawn(move || {
+            for msg in receiver {
+                inner.send(&msg);
+            }
+            inner
+        });
+
+        AsyncMessenger { sender, worker }
+    }
+
+    pub fn shutdown(self) -> M {
+        drop(self.sender);
+        self.worker.join().expect("the messenger thread panicked")
+    }
+}
+
+impl<M: Messenger + Send + 'static> Messenger for AsyncMessenger<M> {
+    fn send(&self, msg: &str) {
+        self.sender
+            .send(String::from(msg))
+            .expect("the messenger thread has stopped");
+    }
+}
+
+#[test]
+fn async_messenger_delivers_everything_in_order() {
+    let messenger = AsyncMessenger::new(WriterMessenger::new(Vec::new()));
+    let mut limit_tracker = LimitTracker::new(&messenger, 100);
+
+    limit_tracker.set_value(80);
+    limit_tracker.set_value(95);
+    limit_tracker.set_value(100);
+    messenger.send("Done for today");
+
+    let delivered = String::from_utf8(messenger.shutdown().into_inner()).unwrap();
+    assert_eq!(
+        delivered.lines().collect::<Vec<&str>>(),
+        vec![
+            "Warning: You've used up over 75% of your quota!",
+            "Urgent warning: You've used up over 90% of your quota!",
+            "Error: You are over your quota!",
+            "Done for today",
+        ]
+    );
+}
+
 // Keeping Track of Borrows at Runtime with RefCell<T>
 // When creating immutable and mutable references, we use the & and &mut syntax, respectively. With RefCell<T>, we use
 // the borrow and borrow_mut methods, which are part of the safe API that belongs to RefCell<T>. The borrow method
@@ -564,6 +1923,36 @@ fn main() {
 // b after = Cons(RefCell { value: 6 }, Cons(RefCell { value: 15 }, Nil))
 // c after = Cons(RefCell { value: 10 }, Cons(RefCell { value: 15 }, Nil))
 
+// Printing with {:?} shows the RefCell wrappers; to read the current values we can walk the list with an iterator
+// instead. iter only borrows each node and calls borrow() on the RefCell as it goes, so it never clones an Rc from the
+// chain and the strong counts stay exactly where they were:
+impl List {
+    fn iter(&self) -> impl Iterator<Item = i32> + '_ {
+        let mut current = self;
+
+        std::iter::from_fn(move || match current {
+            Cons(value, rest) => {
+                current = rest.as_ref();
+                Some(*value.borrow())
+            },
+            Nil => None,
+        })
+    }
+}
+
+#[test]
+fn iter_sees_values_changed_through_refcell() {
+    let value = Rc::new(RefCell::new(5));
+    let a = Rc::new(Cons(Rc::clone(&value), Rc::new(Nil)));
+    let b = Cons(Rc::new(RefCell::new(6)), Rc::clone(&a));
+
+    *value.borrow_mut() += 10;
+
+    assert_eq!(b.iter().collect::<Vec<i32>>(), vec![6, 15]);
+    assert_eq!(Rc::strong_count(&a), 2);
+    assert_eq!(Rc::strong_count(&value), 2);
+}
+
 // Creating a Reference Cycle
 // Let’s look at how a reference cycle might happen and how to prevent it, starting with the definition of the List enum and a
 // tail method:
@@ -762,4 +2151,78 @@ fn main() {
         Rc::strong_count(&leaf),
         Rc::weak_count(&leaf),
     );
-}
\ No newline at end of file
+}
+
+// Printing the counts lets us watch them change, but we can't assert on output. rc_counts returns both counts together,
+// and tree_counts runs the same steps as the main above, recording the counts of each node at every stage instead of
+// printing them:
+fn rc_counts<T>(rc: &Rc<T>) -> (usize, usize) {
+    (Rc::strong_count(rc), Rc::weak_count(rc))
+}
+
+fn tree_counts() -> Vec<(&'static str, (usize, usize))> {
+    let mut counts = Vec::new();
+
+    let leaf = Rc::new(Node {
+        value: 3,
+        parent: RefCell::new(Weak::new()),
+        children: RefCell::new(vec![]),
+    });
+    counts.push(("leaf created", rc_counts(&leaf)));
+
+    {
+        let branch = Rc::new(Node {
+            value: 5,
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(vec![Rc::clone(&leaf)]),
+        });
+        counts.push(("leaf added to branch", rc_counts(&leaf)));
+
+        *leaf.parent.borrow_mut() = Rc::downgrade(&branch);
+        counts.push(("branch after downgrade", rc_counts(&branch)));
+        counts.push(("leaf after downgrade", rc_counts(&leaf)));
+    }
+
+    counts.push(("leaf after branch dropped", rc_counts(&leaf)));
+    counts
+}
+
+#[test]
+fn counts_through_the_tree_scenario() {
+    assert_eq!(
+        tree_counts(),
+        vec![
+            ("leaf created", (1, 0)),
+            ("leaf added to branch", (2, 0)),
+            ("branch after downgrade", (1, 1)),
+            ("leaf after downgrade", (2, 0)),
+            ("leaf after branch dropped", (1, 0)),
+        ]
+    );
+}
+
+// The same idea packages up the pattern from the Rc<T> examples, where clones are made in an inner scope and the count is
+// checked once they're gone. with_rc_scope puts value in a new Rc, runs f with it, and returns f's result along with the
+// strong count after f has returned, by which point any clones f made without returning them have been dropped:
+fn with_rc_scope<T, F: FnOnce(&Rc<T>) -> R, R>(value: T, f: F) -> (R, usize) {
+    let rc = Rc::new(value);
+    let result = f(&rc);
+
+    (result, Rc::strong_count(&rc))
+}
+
+#[test]
+fn clones_inside_the_scope_are_dropped() {
+    let (count_inside, count_after) = with_rc_scope(5, |rc| {
+        let _a = Rc::clone(rc);
+        let _b = Rc::clone(rc);
+        Rc::strong_count(rc)
+    });
+
+    assert_eq!(count_inside, 3);
+    assert_eq!(count_after, 1);
+
+    let (kept, count_after) = with_rc_scope(String::from("shared"), Rc::clone);
+    assert_eq!(*kept, "shared");
+    assert_eq!(count_after, 2);
+}